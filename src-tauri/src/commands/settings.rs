@@ -66,12 +66,208 @@ pub struct Settings {
     pub word_correction_threshold: f64,
     #[serde(default = "default_word_correction_enabled")]
     pub word_correction_enabled: bool,
+    /// Also send `custom_words` to providers as a prompt or keyterms so they are recognized up front
+    #[serde(default = "default_vocabulary_biasing")]
+    pub vocabulary_biasing: bool,
     #[serde(default)]
     pub custom_rewrite_prompt: String,
     #[serde(default = "default_close_to_tray")]
     pub close_to_tray: bool,
     #[serde(default = "default_show_transcript_overlay")]
     pub show_transcript_overlay: bool,
+    /// Send the end of the previous batch segment as context with the next one
+    #[serde(default = "default_segment_context_enabled")]
+    pub segment_context_enabled: bool,
+    /// Keep a connection to the selected streaming provider open between dictations
+    #[serde(default)]
+    pub streaming_prewarm: bool,
+    #[serde(default)]
+    pub custom_transcription: CustomTranscriptionSettings,
+    #[serde(default)]
+    pub custom_rewrite_endpoint: CustomRewriteSettings,
+    /// Options without UI, stored at the top level of settings.json
+    #[serde(flatten)]
+    pub advanced: AdvancedSettings,
+}
+
+/// Options edited in settings.json only. The settings window never sends them, so
+/// `save_settings` keeps the stored values as a whole.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AdvancedSettings {
+    /// Per-provider rewrite model overrides, keyed by rewrite provider id
+    #[serde(default)]
+    pub rewrite_models: HashMap<String, RewriteModelSettings>,
+    /// ggml model file used by the local Whisper provider (looked up in `<app data>/models`)
+    #[serde(default = "default_local_whisper_model")]
+    pub local_whisper_model: String,
+    /// Batch providers tried in order when the selected transcription provider fails
+    #[serde(default)]
+    pub transcription_fallbacks: Vec<String>,
+    /// Rewrite providers tried in order when the selected rewrite provider fails
+    #[serde(default)]
+    pub rewrite_fallbacks: Vec<String>,
+    #[serde(default)]
+    pub retry_policy: RetrySettings,
+    /// Cap on the previous segment's context, in characters
+    #[serde(default = "default_segment_context_max_chars")]
+    pub segment_context_max_chars: usize,
+    /// Batch segments of one recording transcribed at the same time (inserted in order)
+    #[serde(default = "default_max_parallel_segments")]
+    pub max_parallel_segments: usize,
+    /// Save batch segments that fail for lack of network and transcribe them later
+    #[serde(default = "default_offline_queue_enabled")]
+    pub offline_queue_enabled: bool,
+    #[serde(default)]
+    pub hedging: HedgingSettings,
+    #[serde(default)]
+    pub hallucination_filter: HallucinationFilterSettings,
+    /// Voice commands per language code, phrase → action (an empty action removes a built-in)
+    #[serde(default)]
+    pub custom_voice_commands: HashMap<String, HashMap<String, String>>,
+    /// Values for `{name}` placeholders in the text of voice command macros
+    #[serde(default)]
+    pub macro_variables: HashMap<String, String>,
+}
+
+impl Default for AdvancedSettings {
+    fn default() -> Self {
+        Self {
+            rewrite_models: HashMap::new(),
+            local_whisper_model: default_local_whisper_model(),
+            transcription_fallbacks: Vec::new(),
+            rewrite_fallbacks: Vec::new(),
+            retry_policy: RetrySettings::default(),
+            segment_context_max_chars: default_segment_context_max_chars(),
+            max_parallel_segments: default_max_parallel_segments(),
+            offline_queue_enabled: default_offline_queue_enabled(),
+            hedging: HedgingSettings::default(),
+            hallucination_filter: HallucinationFilterSettings::default(),
+            custom_voice_commands: HashMap::new(),
+            macro_variables: HashMap::new(),
+        }
+    }
+}
+
+/// Retry policy for HTTP provider requests (429, 5xx, timeouts, connection failures)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RetrySettings {
+    /// Retries after the first attempt (0 disables retrying)
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Backoff before the first retry, doubled for each further retry
+    #[serde(default = "default_initial_delay_ms")]
+    pub initial_delay_ms: u64,
+    #[serde(default = "default_max_delay_ms")]
+    pub max_delay_ms: u64,
+    /// Overall time budget for a request including all retries
+    #[serde(default = "default_deadline_ms")]
+    pub deadline_ms: u64,
+}
+
+impl Default for RetrySettings {
+    fn default() -> Self {
+        Self {
+            max_retries: default_max_retries(),
+            initial_delay_ms: default_initial_delay_ms(),
+            max_delay_ms: default_max_delay_ms(),
+            deadline_ms: default_deadline_ms(),
+        }
+    }
+}
+
+/// Hedged batch transcription: when the selected provider has not answered after
+/// `delay_ms`, the same segment is also sent to `provider` and the first result wins
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HedgingSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Secondary batch provider id (must differ from the selected provider)
+    #[serde(default)]
+    pub provider: String,
+    #[serde(default = "default_hedging_delay_ms")]
+    pub delay_ms: u64,
+}
+
+impl Default for HedgingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: String::new(),
+            delay_ms: default_hedging_delay_ms(),
+        }
+    }
+}
+
+/// Filter for phrases Whisper-family models invent on silence or noise
+/// ("Thank you for watching", subtitle credits, repetition loops)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HallucinationFilterSettings {
+    #[serde(default = "default_hallucination_filter_enabled")]
+    pub enabled: bool,
+    /// Segments more likely than this to contain no speech are dropped...
+    #[serde(default = "default_no_speech_threshold")]
+    pub no_speech_threshold: f64,
+    /// ...when their mean token log probability is also below this
+    #[serde(default = "default_logprob_threshold")]
+    pub logprob_threshold: f64,
+    /// A word or phrase repeated back to back more often than this is collapsed to one
+    #[serde(default = "default_max_repeats")]
+    pub max_repeats: usize,
+    /// Additional sentences to remove, matched case-insensitively at the start of a sentence
+    #[serde(default)]
+    pub extra_phrases: Vec<String>,
+}
+
+impl Default for HallucinationFilterSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_hallucination_filter_enabled(),
+            no_speech_threshold: default_no_speech_threshold(),
+            logprob_threshold: default_logprob_threshold(),
+            max_repeats: default_max_repeats(),
+            extra_phrases: Vec::new(),
+        }
+    }
+}
+
+/// OpenAI-compatible chat completions endpoint used for text rewrite
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CustomRewriteSettings {
+    /// Base URL including the API version, e.g. "http://localhost:11434/v1"
+    #[serde(default)]
+    pub base_url: String,
+    #[serde(default)]
+    pub model: String,
+    /// Optional; sent as a Bearer token when set
+    #[serde(default)]
+    pub api_key: String,
+}
+
+/// OpenAI-compatible `/audio/transcriptions` endpoint configured by the user
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CustomTranscriptionSettings {
+    /// Base URL including the API version, e.g. "http://localhost:8000/v1"
+    #[serde(default)]
+    pub base_url: String,
+    #[serde(default)]
+    pub model: String,
+    /// Optional; sent as a Bearer token when set
+    #[serde(default)]
+    pub api_key: String,
+    /// Additional multipart form fields sent with every request
+    #[serde(default)]
+    pub extra_fields: HashMap<String, String>,
+}
+
+/// Overrides for a rewrite provider's default model; unset fields keep the provider default
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RewriteModelSettings {
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
 }
 
 #[derive(Clone, Copy)]
@@ -87,6 +283,8 @@ pub struct TrayMenuAnchor {
 
 pub struct TrayMenuAnchorState(pub Mutex<Option<TrayMenuAnchor>>);
 pub struct TrayMenuSizeState(pub Mutex<Option<(f64, f64)>>);
+/// Section ("transcription" or "rewrite") whose API key the settings window focuses once loaded
+pub struct PendingApiKeyFocus(pub Mutex<Option<String>>);
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct WindowPosition {
@@ -273,9 +471,15 @@ impl Default for Settings {
             custom_words: Vec::new(),
             word_correction_threshold: default_word_correction_threshold(),
             word_correction_enabled: default_word_correction_enabled(),
+            vocabulary_biasing: default_vocabulary_biasing(),
             custom_rewrite_prompt: String::new(),
             close_to_tray: default_close_to_tray(),
             show_transcript_overlay: default_show_transcript_overlay(),
+            segment_context_enabled: default_segment_context_enabled(),
+            streaming_prewarm: false,
+            custom_transcription: CustomTranscriptionSettings::default(),
+            custom_rewrite_endpoint: CustomRewriteSettings::default(),
+            advanced: AdvancedSettings::default(),
         }
     }
 }
@@ -284,6 +488,66 @@ fn default_show_transcript_overlay() -> bool {
     true
 }
 
+fn default_local_whisper_model() -> String {
+    "ggml-base.bin".to_string()
+}
+
+fn default_max_retries() -> u32 {
+    2
+}
+
+fn default_initial_delay_ms() -> u64 {
+    500
+}
+
+fn default_max_delay_ms() -> u64 {
+    8_000
+}
+
+fn default_deadline_ms() -> u64 {
+    30_000
+}
+
+fn default_hedging_delay_ms() -> u64 {
+    1_500
+}
+
+fn default_segment_context_enabled() -> bool {
+    true
+}
+
+fn default_segment_context_max_chars() -> usize {
+    200
+}
+
+fn default_offline_queue_enabled() -> bool {
+    true
+}
+
+fn default_max_parallel_segments() -> usize {
+    3
+}
+
+fn default_vocabulary_biasing() -> bool {
+    true
+}
+
+fn default_hallucination_filter_enabled() -> bool {
+    true
+}
+
+fn default_no_speech_threshold() -> f64 {
+    0.6
+}
+
+fn default_logprob_threshold() -> f64 {
+    -1.0
+}
+
+fn default_max_repeats() -> usize {
+    3
+}
+
 fn default_close_to_tray() -> bool {
     true
 }
@@ -339,6 +603,12 @@ async fn save_settings_internal(app: &AppHandle, settings: Settings, emit_event:
         if let Some(main_window) = app.get_webview_window("main") {
             let _ = main_window.emit("settings-changed", ());
         }
+
+        // Recycle the warm streaming connection for the new provider, key or language
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            crate::commands::streaming::refresh_warm_connection(&app).await;
+        });
     }
     
     Ok(())
@@ -347,16 +617,49 @@ async fn save_settings_internal(app: &AppHandle, settings: Settings, emit_event:
 #[tauri::command]
 pub async fn save_settings(app: AppHandle, mut settings: Settings) -> Result<(), String> {
     // Preserve internal state that shouldn't be overwritten by frontend saves
-    // (compact_mode is toggled via toggle_compact_mode, window position is auto-saved)
+    // (compact_mode is toggled via toggle_compact_mode, window position is auto-saved,
+    // advanced options are edited in settings.json only)
     if let Ok(existing) = get_settings(app.clone()).await {
         settings.compact_mode = existing.compact_mode;
         settings.main_window_position = existing.main_window_position;
+        settings.advanced = existing.advanced;
     }
     
     // Public API always emits event (used by settings window)
     save_settings_internal(&app, settings, true).await
 }
 
+/// Open settings on the API key field of the active transcription or rewrite provider
+/// (used when a provider rejects the key)
+#[tauri::command]
+pub async fn open_api_key_settings(
+    app: AppHandle,
+    state: State<'_, PendingApiKeyFocus>,
+    target: String,
+) -> Result<(), String> {
+    let target = if target == "rewrite" { "rewrite" } else { "transcription" };
+
+    // Already loaded: show it if hidden and focus the field directly
+    if let Some(window) = app.get_webview_window("settings") {
+        if !window.is_visible().map_err(|e| e.to_string())? {
+            open_settings_window(app.clone()).await?;
+        }
+        let _ = window.eval(format!("window.focusApiKey && window.focusApiKey('{}');", target));
+        let _ = window.set_focus();
+        return Ok(());
+    }
+
+    // New window: it picks the target up after loading its settings
+    *state.0.lock().map_err(|_| "PendingApiKeyFocus lock poisoned")? = Some(target.to_string());
+    open_settings_window(app).await
+}
+
+/// Take the API key focus requested by `open_api_key_settings`, if any
+#[tauri::command]
+pub fn take_pending_api_key_focus(state: State<'_, PendingApiKeyFocus>) -> Option<String> {
+    state.0.lock().ok()?.take()
+}
+
 #[tauri::command]
 pub async fn open_settings_window(app: AppHandle) -> Result<(), String> {
     if let Some(tray_menu_wnd) = app.get_webview_window("tray_menu") {
@@ -1152,3 +1455,18 @@ pub async fn reposition_transcript_overlay(app: AppHandle) -> Result<(), String>
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_advanced_settings_stay_top_level() {
+        let json = r#"{"groq_api_key": "", "rewrite_models": {"groq": {"temperature": 0.5}}}"#;
+        let settings: Settings = serde_json::from_str(json).unwrap();
+        assert_eq!(settings.advanced.rewrite_models["groq"].temperature, Some(0.5));
+
+        let value = serde_json::to_value(&settings).unwrap();
+        assert_eq!(value["rewrite_models"]["groq"]["temperature"], 0.5);
+        assert!(value.get("advanced").is_none());
+    }
+}
@@ -1,684 +1,489 @@
 use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::mpsc::Receiver;
 use tokio::sync::Mutex;
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::providers;
-use crate::services;
-use crate::voice_commands::{VoiceCommands, process_voice_commands, CommandAction};
+use crate::providers::streaming::{self as stream, AudioSink, StreamConfig, StreamEvent};
+use crate::providers::vocabulary;
+use crate::providers::warm::{self, WarmPool};
+use crate::providers::supervisor;
+use crate::providers::ProviderError;
+use crate::services::command_executor;
+use crate::services::transcript_pipeline::{PipelineOptions, PipelineOutput, TranscriptPipeline};
+use crate::voice_commands::{CommandPacks, VoiceCommands};
 
 // Global state for active streaming sessions
-type AudioSender = tokio::sync::mpsc::Sender<Vec<u8>>;
-type StreamingSessions = Arc<Mutex<HashMap<String, AudioSender>>>;
+type StreamingSessions = Arc<Mutex<HashMap<String, AudioSink>>>;
 
 // Store streaming sessions in app state
 pub struct StreamingState {
     pub sessions: StreamingSessions,
+    /// Pre-opened connection for the next session (`Settings.streaming_prewarm`)
+    pub warm: Arc<WarmPool>,
 }
 
 impl Default for StreamingState {
     fn default() -> Self {
         Self {
             sessions: Arc::new(Mutex::new(HashMap::new())),
+            warm: Arc::new(WarmPool::default()),
         }
     }
 }
 
+/// Open (or recycle) the warm streaming connection to match the current settings,
+/// or close it when pre-warming is off or a batch provider is selected
+pub async fn refresh_warm_connection(app: &AppHandle) {
+    let Some(state) = app.try_state::<StreamingState>() else {
+        return;
+    };
+    let settings = crate::commands::settings::get_settings_sync(app).unwrap_or_default();
+    match warm::warm_target(&settings) {
+        Some((provider, config)) => state.warm.fill(provider, config).await,
+        None => state.warm.clear().await,
+    }
+}
+
+/// Per-session options shared by the event consumers
+struct SessionOptions {
+    session_id: String,
+    insertion_mode: String,
+    smart_format: bool,
+    voice_commands_enabled: bool,
+    voice_lang: String,
+}
+
+/// Payload for `streaming-error`
+#[derive(Clone, serde::Serialize)]
+struct StreamingErrorPayload {
+    session_id: String,
+    error: ProviderError,
+}
+
+/// Payload for `streaming-reconnecting`
+#[derive(Clone, serde::Serialize)]
+struct StreamingReconnectingPayload {
+    session_id: String,
+    attempt: u32,
+}
+
+/// How the frontend asks a streaming session to transcribe
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamingOptions {
+    /// Language code, or "multi" for auto-detect
+    pub language: String,
+    pub smart_format: bool,
+    /// Audio encoding the frontend sends (None: provider default)
+    #[serde(default)]
+    pub encoding: Option<String>,
+    #[serde(default)]
+    pub voice_commands_enabled: Option<bool>,
+}
+
 /// Start streaming transcription session
 #[tauri::command]
 pub async fn start_streaming_transcription(
     app: AppHandle,
     provider: String,
     api_key: String,
-    language: String,
-    smart_format: bool,
     insertion_mode: String,
-    encoding: Option<String>,
-    voice_commands_enabled: Option<bool>,
-) -> Result<String, String> {
+    options: StreamingOptions,
+) -> Result<String, ProviderError> {
+    let StreamingOptions {
+        language,
+        smart_format,
+        encoding,
+        voice_commands_enabled,
+    } = options;
+
     // Get or create streaming state
     let state = app.state::<StreamingState>();
     
     // Check if provider is supported
-    let session_id = format!("{}_{}", provider, chrono::Utc::now().timestamp());
+    let streaming_provider = stream::provider(&provider)
+        .ok_or_else(|| format!("Unsupported streaming provider: {}", provider))?;
+    let session_id = format!("{}_{}", streaming_provider.id(), chrono::Utc::now().timestamp_millis());
     
-    match provider.as_str() {
-        "deepgram" => {
-            // Start Deepgram streaming
-            // Clone language for voice commands before passing ownership to start_streaming
-            let voice_lang = language.clone();
-            
-            let (audio_tx, mut transcript_rx, mut partial_rx) = providers::deepgram::start_streaming(
-                api_key,
-                language,
-                smart_format,
-                encoding,
-            )
-            .await
-            .map_err(|e| format!("Failed to start Deepgram: {}", e))?;
-            
-            // Store audio sender for this session
-            {
-                let mut sessions = state.sessions.lock().await;
-                sessions.insert(session_id.clone(), audio_tx);
+    // Returns at once: audio is buffered until the handshake completes (skipped when
+    // a warm connection matches), and dropped sockets are reconnected.
+    // Connection failures arrive as `streaming-error`.
+    let vocabulary = crate::commands::settings::get_settings_sync(&app)
+        .map(|settings| vocabulary::from_settings(&settings))
+        .unwrap_or_default();
+    let config = StreamConfig::for_provider(
+        streaming_provider.as_ref(),
+        api_key,
+        &language,
+        smart_format,
+        encoding,
+        vocabulary,
+    );
+    // Auto-detect ('multi' or empty) uses English voice commands
+    let voice_lang = config.language.clone().unwrap_or_else(|| "en".to_string());
+    let warm = state.warm.take(streaming_provider.id(), &config).await;
+    let session = supervisor::connect(streaming_provider.clone(), config, warm);
+    
+    // Store audio sink for this session
+    {
+        let mut sessions = state.sessions.lock().await;
+        sessions.insert(session_id.clone(), session.sink);
+    }
+    
+    let options = SessionOptions {
+        session_id: session_id.clone(),
+        insertion_mode,
+        smart_format,
+        voice_commands_enabled: voice_commands_enabled.unwrap_or(true),
+        voice_lang,
+    };
+    
+    // Spawn task to handle incoming events
+    let app_clone = app.clone();
+    let sessions_clone = state.sessions.clone();
+    let word_deltas = streaming_provider.emits_word_deltas();
+    let mut events = session.events;
+    tokio::spawn(async move {
+        let pipeline = build_pipeline(&app_clone, &options).await;
+        if word_deltas {
+            consume_word_delta_events(app_clone.clone(), &mut events, &pipeline, &options).await;
+        } else {
+            consume_utterance_events(&app_clone, &mut events, &pipeline, &options).await;
+        }
+        
+        // Clean up session when done. If it is still registered, the socket closed
+        // before the frontend stopped it, so let the main window know.
+        let mut sessions = sessions_clone.lock().await;
+        if sessions.remove(&options.session_id).is_some() {
+            if let Some(window) = app_clone.get_webview_window("main") {
+                let _ = window.emit("streaming-closed", &options.session_id);
             }
-            
-            // Spawn task to forward partial transcripts to overlay
-            let app_partial = app.clone();
-            tokio::spawn(async move {
-                let noise_re = regex::Regex::new(r"\[.*?\]|\(.*?\)").unwrap();
-                let space_re = regex::Regex::new(r"\s+").unwrap();
-                while let Some(partial_text) = partial_rx.recv().await {
-                    let cleaned = noise_re.replace_all(&partial_text, " ");
-                    let cleaned = space_re.replace_all(&cleaned, " ").trim().to_string();
-                    
-                    if let Some(window) = app_partial.get_webview_window("main") {
-                        let _ = window.emit("streaming-partial-transcript", &cleaned);
-                    }
+        }
+        drop(sessions);
+        
+        // Have a connection ready for the next dictation
+        refresh_warm_connection(&app_clone).await;
+    });
+    
+    Ok(session_id)
+}
+
+/// Consume events from providers that send whole utterances (Deepgram, Cartesia, ElevenLabs)
+async fn consume_utterance_events(
+    app: &AppHandle,
+    events: &mut Receiver<StreamEvent>,
+    pipeline: &TranscriptPipeline,
+    options: &SessionOptions,
+) {
+    while let Some(event) = events.recv().await {
+        match event {
+            StreamEvent::Partial(partial_text) => {
+                // Forward partial transcripts to overlay
+                let cleaned = pipeline.clean_noise(&partial_text);
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.emit("streaming-partial-transcript", &cleaned);
                 }
-            });
-            
-            // Spawn task to handle incoming transcripts
-            let app_clone = app.clone();
-            let session_id_clone = session_id.clone();
-            let sessions_clone = state.sessions.clone();
-            
-            let voice_cmds_enabled = voice_commands_enabled.unwrap_or(true);
-            // voice_lang is already cloned above
-            tokio::spawn(async move {
-                while let Some(transcript) = transcript_rx.recv().await {
-                    // Clear partial overlay when committed text arrives
-                    if let Some(window) = app_clone.get_webview_window("main") {
-                        let _ = window.emit("streaming-partial-clear", ());
-                    }
-                    
-                    // Apply word correction if custom words are configured
-                    let corrected_transcript = if let Ok(settings) = crate::commands::settings::get_settings(app_clone.clone()).await {
-                        if settings.word_correction_enabled {
-                            apply_word_correction_sync(&transcript, &settings.custom_words, settings.word_correction_threshold)
-                        } else {
-                            transcript.clone()
-                        }
-                    } else {
-                        transcript.clone()
-                    };
-                    
-                    // Process voice commands if enabled
-                    if voice_cmds_enabled {
-                        let voice_commands = VoiceCommands::new_with_language(&voice_lang);
-                        let processed = process_voice_commands(&corrected_transcript, &voice_commands);
-                        
-                        // Execute command actions
-                        for action in &processed.actions {
-                            if let Err(e) = execute_streaming_command_action(action, &app_clone).await {
-                                eprintln!("[Voice Commands] Failed to execute action: {}", e);
-                            }
-                        }
-                        
-                        // Insert remaining text
-                        let text_to_insert = if processed.remaining_text.is_empty() {
-                            processed.processed_text.clone()
-                        } else if processed.processed_text.is_empty() {
-                            if processed.had_key_action {
-                                processed.remaining_text.clone()
-                            } else {
-                                format!("{} ", processed.remaining_text)
-                            }
-                        } else {
-                            format!("{}{}", processed.remaining_text, processed.processed_text)
-                        };
-                        
-                        if !text_to_insert.is_empty() {
-                            let _ = insert_transcript_text(&text_to_insert, &insertion_mode, &app).await;
-                        }
-                    } else {
-                        // No voice commands - insert directly with space
-                        let transcript_with_space = format!("{} ", corrected_transcript);
-                        let _ = insert_transcript_text(&transcript_with_space, &insertion_mode, &app).await;
-                    }
-                    
-                    // Emit event to frontend for status update
-                    if let Some(window) = app_clone.get_webview_window("main") {
-                        let _ = window.emit("streaming-transcript", corrected_transcript);
-                    }
+            }
+            StreamEvent::Final(transcript) => {
+                // Clear partial overlay when committed text arrives
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.emit("streaming-partial-clear", ());
                 }
                 
-                // Clean up session when done
-                let mut sessions = sessions_clone.lock().await;
-                sessions.remove(&session_id_clone);
-            });
-            
-            Ok(session_id)
+                let output = pipeline.process_utterance(&transcript);
+                apply_pipeline_output(&output, &options.insertion_mode, app).await;
+            }
+            StreamEvent::Error(error) => emit_stream_error(app, &options.session_id, error),
+            StreamEvent::Reconnecting { attempt } => emit_reconnecting(app, &options.session_id, attempt),
+            StreamEvent::Reconnected => emit_reconnected(app, &options.session_id),
+            StreamEvent::SpeechStarted => emit_session_event(app, "streaming-speech-started", &options.session_id),
+            StreamEvent::UtteranceEnd => emit_session_event(app, "streaming-utterance-end", &options.session_id),
+            StreamEvent::Closed => break,
         }
-        "cartesia" => {
-            // Start Cartesia streaming
-            // Clone language for voice commands before cart_language takes ownership
-            let voice_lang = if language == "multi" || language.is_empty() {
-                "en".to_string()
-            } else {
-                language.clone()
-            };
+    }
+}
+
+/// Wait for the next text delta, reporting errors along the way.
+/// Returns None once the stream is closed. Cancel-safe, so it can be raced against a timeout.
+async fn next_word_delta(
+    events: &mut Receiver<StreamEvent>,
+    app: &AppHandle,
+    session_id: &str,
+) -> Option<String> {
+    loop {
+        match events.recv().await? {
+            StreamEvent::Final(text) => return Some(text),
+            StreamEvent::Error(error) => emit_stream_error(app, session_id, error),
+            StreamEvent::Reconnecting { attempt } => emit_reconnecting(app, session_id, attempt),
+            StreamEvent::Reconnected => emit_reconnected(app, session_id),
+            StreamEvent::Closed => return None,
+            StreamEvent::Partial(_) | StreamEvent::SpeechStarted | StreamEvent::UtteranceEnd => {}
+        }
+    }
+}
+
+/// Consume events from providers that send word fragments (Voxtral).
+/// Fragments are assembled into words, and words are held while they could
+/// still form a voice command.
+#[allow(unused_assignments)]
+async fn consume_word_delta_events(
+    app_clone: AppHandle,
+    events: &mut Receiver<StreamEvent>,
+    pipeline: &TranscriptPipeline,
+    options: &SessionOptions,
+) {
+    let insertion_mode = options.insertion_mode.clone();
+    let voice_cmds_enabled = pipeline.voice_commands().is_some();
+    
+    // Word buffer for multi-word voice command detection.
+    // Voxtral sends one word per delta, so we accumulate words here
+    // and check if they form a voice command before inserting text.
+    let mut pending_buffer = String::new();
+    let mut pending_leading_space = false;
+    
+    // After a voice command, suppress trailing punctuation (period/comma)
+    // that the model adds because the user paused speaking.
+    #[allow(unused_assignments)]
+    let mut last_command_time: Option<tokio::time::Instant> = None;
+    
+    // Helper closure removed as overlay is disabled for Voxtral
+    let emit_clear = |_app: &AppHandle| {
+        // No-op
+    };
+    
+    // === Two-tier buffer for Voxtral syllable fragmentation ===
+    //
+    // Tier 1: Fragment assembly (`current_word`)
+    //   Voxtral sends syllable fragments: "ex" + "pect" + "ation"
+    //   Fragments without a leading space are concatenated.
+    //   When a space-prefixed delta arrives, the previous word is complete.
+    //
+    // Tier 2: Word buffer (`pending_buffer`)
+    //   Completed words are pushed here for voice command matching.
+    //   Only complete words are checked against commands & word correction.
+    
+    let mut current_word = String::new();       // Tier 1: syllable fragment assembly
+    #[allow(unused_assignments)]
+    let mut current_word_has_leading_space = false;
+    
+    // Helper: push a completed word into pending_buffer and check commands
+    #[allow(unused_assignments)]
+    macro_rules! push_completed_word {
+        ($completed:expr, $had_leading_space:expr) => {{
+            let completed_word: String = $completed;
+            let word_leading: bool = $had_leading_space;
             
-            // Cartesia uses raw language code (omit for multilingual)
-            let cart_language = if language == "multi" || language.is_empty() {
-                None
+            if completed_word.is_empty() {
+                // Nothing to push
+            } else if !voice_cmds_enabled {
+                // No voice commands: insert the word directly
+                flush_voxtral_text(pipeline, &completed_word, word_leading, &insertion_mode, &app_clone).await;
+                emit_clear(&app_clone);
             } else {
-                Some(language)
-            };
-            
-            let (audio_tx, mut transcript_rx, mut partial_rx) = providers::cartesia::start_streaming(
-                api_key,
-                cart_language,
-            )
-            .await
-            .map_err(|e| format!("Failed to start Cartesia: {}", e))?;
-            
-            // Store audio sender for this session
-            {
-                let mut sessions = state.sessions.lock().await;
-                sessions.insert(session_id.clone(), audio_tx);
-            }
-            
-            // Spawn task to forward partial transcripts to overlay
-            let app_partial = app.clone();
-            tokio::spawn(async move {
-                let noise_re = regex::Regex::new(r"\[.*?\]|\(.*?\)").unwrap();
-                let space_re = regex::Regex::new(r"\s+").unwrap();
-                while let Some(partial_text) = partial_rx.recv().await {
-                    let cleaned = noise_re.replace_all(&partial_text, " ");
-                    let cleaned = space_re.replace_all(&cleaned, " ").trim().to_string();
-                    
-                    if let Some(window) = app_partial.get_webview_window("main") {
-                        let _ = window.emit("streaming-partial-transcript", &cleaned);
+                // Voice commands enabled: push to word buffer
+                let vc = pipeline.voice_commands().unwrap();
+                
+                // Check if this is post-command punctuation to suppress
+                let mut skip = false;
+                if let Some(cmd_time) = last_command_time {
+                    if cmd_time.elapsed() < tokio::time::Duration::from_millis(500) {
+                        let trimmed_lower = completed_word.trim().to_lowercase();
+                        if trimmed_lower == "." || trimmed_lower == "," {
+                            last_command_time = None;
+                            skip = true;
+                        }
+                    }
+                    if !skip {
+                        last_command_time = None;
                     }
                 }
-            });
-            
-            // Spawn task to handle incoming transcripts
-            let app_clone = app.clone();
-            let session_id_clone = session_id.clone();
-            let sessions_clone = state.sessions.clone();
-            
-            let voice_cmds_enabled = voice_commands_enabled.unwrap_or(true);
-            tokio::spawn(async move {
-                while let Some(transcript) = transcript_rx.recv().await {
-                    // Clear partial overlay when committed text arrives
-                    if let Some(window) = app_clone.get_webview_window("main") {
-                        let _ = window.emit("streaming-partial-clear", ());
+                
+                if !skip {
+                    // Track leading space
+                    if pending_buffer.is_empty() {
+                        pending_leading_space = word_leading;
                     }
                     
-                    // Apply formatting based on smart_format setting
-                    let formatted_transcript = if smart_format {
-                        transcript
+                    // Append completed word to pending buffer
+                    if pending_buffer.is_empty() {
+                        pending_buffer = completed_word;
                     } else {
-                        // Normalize like Electron app: lowercase + remove punctuation
-                        normalize_whisper_transcript(&transcript)
-                    };
-                    
-                    // Apply word correction if custom words are configured
-                    // Apply word correction if custom words are configured
-                    let corrected_transcript = if let Ok(settings) = crate::commands::settings::get_settings(app_clone.clone()).await {
-                        if settings.word_correction_enabled {
-                            apply_word_correction_sync(&formatted_transcript, &settings.custom_words, settings.word_correction_threshold)
-                        } else {
-                            formatted_transcript.clone()
-                        }
-                    } else {
-                        formatted_transcript.clone()
-                    };
+                        pending_buffer = format!("{} {}", pending_buffer, completed_word);
+                    }
                     
-                    // Process voice commands if enabled
-                    if voice_cmds_enabled {
-                        let voice_commands = VoiceCommands::new_with_language(&voice_lang);
-                        let processed = process_voice_commands(&corrected_transcript, &voice_commands);
-                        
-                        // Execute command actions
-                        for action in &processed.actions {
-                            if let Err(e) = execute_streaming_command_action(action, &app_clone).await {
-                                eprintln!("[Voice Commands] Failed to execute action: {}", e);
-                            }
-                        }
-                        
-                        // Insert remaining text
-                        let text_to_insert = if processed.remaining_text.is_empty() {
-                            processed.processed_text.clone()
-                        } else if processed.processed_text.is_empty() {
-                            if processed.had_key_action {
-                                processed.remaining_text.clone()
-                            } else {
-                                format!("{} ", processed.remaining_text)
-                            }
+                    // Check buffer against voice commands
+                    if vc.is_exact_command(&pending_buffer) && !vc.is_command_prefix(&pending_buffer) {
+                        let buffer_text = if let Some(cmd) = vc.reconstruct_command(&pending_buffer) {
+                            pending_buffer.clear();
+                            cmd
                         } else {
-                            format!("{}{}", processed.remaining_text, processed.processed_text)
+                            std::mem::take(&mut pending_buffer)
                         };
+                        let leading = pending_leading_space;
+                        pending_leading_space = false;
                         
-                        if !text_to_insert.is_empty() {
-                            let _ = insert_transcript_text(&text_to_insert, &insertion_mode, &app_clone).await;
+                        let had_cmd = flush_voxtral_text(
+                            pipeline, &buffer_text, leading, &insertion_mode, &app_clone
+                        ).await;
+                        if had_cmd {
+                            last_command_time = Some(tokio::time::Instant::now());
                         }
+                        emit_clear(&app_clone);
+                    } else if vc.is_command_prefix(&pending_buffer) || vc.is_exact_command(&pending_buffer) {
+                        // Could become a longer command → hold the buffer
                     } else {
-                        // No voice commands - insert directly with space
-                        let transcript_with_space = format!("{} ", corrected_transcript);
-                        let _ = insert_transcript_text(&transcript_with_space, &insertion_mode, &app_clone).await;
-                    }
-                    
-                    // Emit event to frontend for status update
-                    if let Some(window) = app_clone.get_webview_window("main") {
-                        let _ = window.emit("streaming-transcript", corrected_transcript);
-                    }
-                }
-                
-                // Clean up session when done
-                let mut sessions = sessions_clone.lock().await;
-                sessions.remove(&session_id_clone);
-            });
-            
-            Ok(session_id)
-        }
-        "voxtral" => {
-            // Start Voxtral realtime streaming (no language param - auto-detected)
-            // Default voice commands to English since Voxtral auto-detects
-            let voice_lang = if language == "multi" || language.is_empty() {
-                "en".to_string()
-            } else {
-                language.clone()
-            };
-            
-            let (audio_tx, mut transcript_rx) = providers::voxtral::start_streaming(
-                api_key,
-            )
-            .await
-            .map_err(|e| format!("Failed to start Voxtral: {}", e))?;
-            
-            // Store audio sender for this session
-            {
-                let mut sessions = state.sessions.lock().await;
-                sessions.insert(session_id.clone(), audio_tx);
-            }
-            
-            // Spawn task to handle incoming stable transcripts (or fast in single-stream mode)
-            let app_clone = app.clone();
-            let session_id_clone = session_id.clone();
-            let sessions_clone = state.sessions.clone();
-            
-            let voice_cmds_enabled = voice_commands_enabled.unwrap_or(true);
-            #[allow(unused_assignments)]
-            tokio::spawn(async move {
-                // Word buffer for multi-word voice command detection.
-                // Voxtral sends one word per delta, so we accumulate words here
-                // and check if they form a voice command before inserting text.
-                let mut pending_buffer = String::new();
-                let mut pending_leading_space = false;
-                
-                // After a voice command, suppress trailing punctuation (period/comma)
-                // that the model adds because the user paused speaking.
-                #[allow(unused_assignments)]
-                let mut last_command_time: Option<tokio::time::Instant> = None;
-                
-                let voice_commands = if voice_cmds_enabled {
-                    Some(VoiceCommands::new_with_language(&voice_lang))
-                } else {
-                    None
-                };
-                
-                // Helper closure removed as overlay is disabled for Voxtral
-                let emit_clear = |_app: &AppHandle| {
-                    // No-op
-                };
-                
-                // === Two-tier buffer for Voxtral syllable fragmentation ===
-                //
-                // Tier 1: Fragment assembly (`current_word`)
-                //   Voxtral sends syllable fragments: "ex" + "pect" + "ation"
-                //   Fragments without a leading space are concatenated.
-                //   When a space-prefixed delta arrives, the previous word is complete.
-                //
-                // Tier 2: Word buffer (`pending_buffer`)
-                //   Completed words are pushed here for voice command matching.
-                //   Only complete words are checked against commands & word correction.
-                
-                let mut current_word = String::new();       // Tier 1: syllable fragment assembly
-                #[allow(unused_assignments)]
-                let mut current_word_has_leading_space = false;
-                
-                // Helper: push a completed word into pending_buffer and check commands
-                #[allow(unused_assignments)]
-                macro_rules! push_completed_word {
-                    ($completed:expr, $had_leading_space:expr) => {{
-                        let completed_word: String = $completed;
-                        let word_leading: bool = $had_leading_space;
+                        // Not a command → flush immediately
+                        let buffer_text = std::mem::take(&mut pending_buffer);
+                        let leading = pending_leading_space;
+                        pending_leading_space = false;
                         
-                        if completed_word.is_empty() {
-                            // Nothing to push
-                        } else if !voice_cmds_enabled {
-                            // No voice commands: insert the word directly
-                            let formatted = if smart_format {
-                                completed_word.clone()
-                            } else {
-                                normalize_whisper_transcript(&completed_word)
-                            };
-                            let corrected = apply_word_correction_if_needed(&formatted, &app_clone).await;
-                            let final_text = if word_leading && !corrected.starts_with(' ') {
-                                format!(" {}", corrected)
-                            } else {
-                                corrected.clone()
-                            };
-                            let _ = insert_transcript_text(&final_text, &insertion_mode, &app_clone).await;
-                            
-                            if let Some(window) = app_clone.get_webview_window("main") {
-                                let _ = window.emit("streaming-transcript", &corrected);
-                            }
-                            emit_clear(&app_clone);
-                        } else {
-                            // Voice commands enabled: push to word buffer
-                            let vc = voice_commands.as_ref().unwrap();
-                            
-                            // Check if this is post-command punctuation to suppress
-                            let mut skip = false;
-                            if let Some(cmd_time) = last_command_time {
-                                if cmd_time.elapsed() < tokio::time::Duration::from_millis(500) {
-                                    let trimmed_lower = completed_word.trim().to_lowercase();
-                                    if trimmed_lower == "." || trimmed_lower == "," {
-                                        last_command_time = None;
-                                        skip = true;
-                                    }
-                                }
-                                if !skip {
-                                    last_command_time = None;
-                                }
-                            }
-                            
-                            if !skip {
-                                // Track leading space
-                                if pending_buffer.is_empty() {
-                                    pending_leading_space = word_leading;
-                                }
-                                
-                                // Append completed word to pending buffer
-                                if pending_buffer.is_empty() {
-                                    pending_buffer = completed_word;
-                                } else {
-                                    pending_buffer = format!("{} {}", pending_buffer, completed_word);
-                                }
-                                
-                                // Check buffer against voice commands
-                                if vc.is_exact_command(&pending_buffer) && !vc.is_command_prefix(&pending_buffer) {
-                                    let buffer_text = if let Some(cmd) = vc.reconstruct_command(&pending_buffer) {
-                                        pending_buffer.clear();
-                                        cmd
-                                    } else {
-                                        std::mem::take(&mut pending_buffer)
-                                    };
-                                    let leading = pending_leading_space;
-                                    pending_leading_space = false;
-                                    
-                                    let had_cmd = flush_voxtral_text(
-                                        &buffer_text, leading, smart_format,
-                                        &insertion_mode, &app_clone, &voice_commands
-                                    ).await;
-                                    if had_cmd {
-                                        last_command_time = Some(tokio::time::Instant::now());
-                                    }
-                                    emit_clear(&app_clone);
-                                } else if vc.is_command_prefix(&pending_buffer) || vc.is_exact_command(&pending_buffer) {
-                                    // Could become a longer command → hold the buffer
-                                } else {
-                                    // Not a command → flush immediately
-                                    let buffer_text = std::mem::take(&mut pending_buffer);
-                                    let leading = pending_leading_space;
-                                    pending_leading_space = false;
-                                    
-                                    let had_cmd = flush_voxtral_text(
-                                        &buffer_text, leading, smart_format,
-                                        &insertion_mode, &app_clone, &voice_commands
-                                    ).await;
-                                    if had_cmd {
-                                        last_command_time = Some(tokio::time::Instant::now());
-                                    }
-                                    emit_clear(&app_clone);
-                                }
-                            }
+                        let had_cmd = flush_voxtral_text(
+                            pipeline, &buffer_text, leading, &insertion_mode, &app_clone
+                        ).await;
+                        if had_cmd {
+                            last_command_time = Some(tokio::time::Instant::now());
                         }
-                    }};
+                        emit_clear(&app_clone);
+                    }
                 }
-                
-                loop {
-                    // Determine timeout: use shorter timeout for fragment assembly,
-                    // longer timeout for voice command buffer
-                    let needs_timeout = !current_word.is_empty() || (!pending_buffer.is_empty() && voice_cmds_enabled);
-                    let timeout_ms = if !current_word.is_empty() { 300 } else { 700 };
-                    
-                    let recv_result = if needs_timeout {
-                        match tokio::time::timeout(
-                            tokio::time::Duration::from_millis(timeout_ms),
-                            transcript_rx.recv()
-                        ).await {
-                            Ok(result) => result,
-                            Err(_) => {
-                                // Timeout fired. Two cases:
-                                // 1) current_word was non-empty (300ms timeout) → push it, but
-                                //    do NOT flush pending_buffer yet. Let it get its own 700ms timeout.
-                                // 2) current_word was empty (700ms timeout) → flush pending_buffer.
-                                
-                                let had_current_word = !current_word.is_empty();
-                                
-                                if had_current_word {
-                                    let word = std::mem::take(&mut current_word);
-                                    let leading = current_word_has_leading_space;
-                                    current_word_has_leading_space = false;
-                                    push_completed_word!(word, leading);
-                                    // Do NOT flush pending_buffer here — it may be holding
-                                    // a command prefix like "question" waiting for "mark".
-                                    // The next loop iteration will set a 700ms timeout for it.
-                                }
-                                
-                                // Only flush pending_buffer on a dedicated 700ms timeout
-                                // (i.e., when current_word was already empty at timeout)
-                                if !had_current_word && !pending_buffer.is_empty() {
-                                    let buffer_text = std::mem::take(&mut pending_buffer);
-                                    let leading = pending_leading_space;
-                                    pending_leading_space = false;
-                                    
-                                    let had_cmd = flush_voxtral_text(
-                                        &buffer_text, leading, smart_format,
-                                        &insertion_mode, &app_clone, &voice_commands
-                                    ).await;
-                                    if had_cmd {
-                                        last_command_time = Some(tokio::time::Instant::now());
-                                    }
-                                    emit_clear(&app_clone);
-                                }
-                                
-                                continue;
-                            }
-                        }
-                    } else {
-                        transcript_rx.recv().await
-                    };
+            }
+        }};
+    }
+    
+    loop {
+        // Determine timeout: use shorter timeout for fragment assembly,
+        // longer timeout for voice command buffer
+        let needs_timeout = !current_word.is_empty() || (!pending_buffer.is_empty() && voice_cmds_enabled);
+        let timeout_ms = if !current_word.is_empty() { 300 } else { 700 };
+        
+        let recv_result = if needs_timeout {
+            match tokio::time::timeout(
+                tokio::time::Duration::from_millis(timeout_ms),
+                next_word_delta(events, &app_clone, &options.session_id)
+            ).await {
+                Ok(result) => result,
+                Err(_) => {
+                    // Timeout fired. Two cases:
+                    // 1) current_word was non-empty (300ms timeout) → push it, but
+                    //    do NOT flush pending_buffer yet. Let it get its own 700ms timeout.
+                    // 2) current_word was empty (700ms timeout) → flush pending_buffer.
                     
-                    let raw_transcript = match recv_result {
-                        Some(t) => t,
-                        None => break, // Channel closed
-                    };
+                    let had_current_word = !current_word.is_empty();
                     
-                    if raw_transcript.is_empty() {
-                        continue;
+                    if had_current_word {
+                        let word = std::mem::take(&mut current_word);
+                        let leading = current_word_has_leading_space;
+                        current_word_has_leading_space = false;
+                        push_completed_word!(word, leading);
+                        // Do NOT flush pending_buffer here — it may be holding
+                        // a command prefix like "question" waiting for "mark".
+                        // The next loop iteration will set a 700ms timeout for it.
                     }
                     
-                    // Replace newlines with spaces to treat them as word boundaries
-                    let transcript = raw_transcript.replace('\n', " ").replace('\r', "");
-                    
-                    // A Voxtral transcript delta might contain multiple words (e.g. " Select all.").
-                    // We split by space to ensure we assemble fragments and emit complete words singly.
-                    let mut splits = transcript.split(' ');
-                    
-                    if let Some(first) = splits.next() {
-                        if !first.is_empty() {
-                            current_word.push_str(first);
-                        }
+                    // Only flush pending_buffer on a dedicated 700ms timeout
+                    // (i.e., when current_word was already empty at timeout)
+                    if !had_current_word && !pending_buffer.is_empty() {
+                        let buffer_text = std::mem::take(&mut pending_buffer);
+                        let leading = pending_leading_space;
+                        pending_leading_space = false;
                         
-                        for part in splits {
-                            if !current_word.is_empty() {
-                                let completed = std::mem::take(&mut current_word);
-                                let leading = current_word_has_leading_space;
-                                push_completed_word!(completed, leading);
-                            }
-                            
-                            current_word = part.to_string();
-                            current_word_has_leading_space = true;
+                        let had_cmd = flush_voxtral_text(
+                            pipeline, &buffer_text, leading, &insertion_mode, &app_clone
+                        ).await;
+                        if had_cmd {
+                            last_command_time = Some(tokio::time::Instant::now());
                         }
+                        emit_clear(&app_clone);
                     }
-                }
-                
-                // Flush any remaining fragments and buffer on session end
-                if !current_word.is_empty() {
-                    let word = std::mem::take(&mut current_word);
-                    let leading = current_word_has_leading_space;
-                    push_completed_word!(word, leading);
-                }
-                if !pending_buffer.is_empty() {
-                    let buffer_text = std::mem::take(&mut pending_buffer);
-                    let leading = pending_leading_space;
                     
-                    flush_voxtral_text(
-                        &buffer_text, leading, smart_format,
-                        &insertion_mode, &app_clone, &voice_commands
-                    ).await;
+                    continue;
                 }
-                
-                // Clean up session when done
-                let mut sessions = sessions_clone.lock().await;
-                sessions.remove(&session_id_clone);
-            });
-            
-            Ok(session_id)
+            }
+        } else {
+            next_word_delta(events, &app_clone, &options.session_id).await
+        };
+        
+        let raw_transcript = match recv_result {
+            Some(t) => t,
+            None => break, // Channel closed
+        };
+        
+        if raw_transcript.is_empty() {
+            continue;
         }
-        "elevenlabs" => {
-            // Start ElevenLabs Scribe v2 realtime streaming
-            // Clone language for voice commands before el_language takes ownership
-            let voice_lang = if language == "multi" || language.is_empty() {
-                "en".to_string()
-            } else {
-                language.clone()
-            };
-            
-            // ElevenLabs uses language_code param (omit for multilingual/auto-detect)
-            let el_language = if language == "multi" || language.is_empty() {
-                None
-            } else {
-                Some(language)
-            };
-            
-            let (audio_tx, mut transcript_rx, mut partial_rx) = providers::elevenlabs::start_streaming(
-                api_key,
-                el_language,
-            )
-            .await
-            .map_err(|e| format!("Failed to start ElevenLabs: {}", e))?;
-            
-            // Store audio sender for this session
-            {
-                let mut sessions = state.sessions.lock().await;
-                sessions.insert(session_id.clone(), audio_tx);
+        
+        // Replace newlines with spaces to treat them as word boundaries
+        let transcript = raw_transcript.replace('\n', " ").replace('\r', "");
+        
+        // A Voxtral transcript delta might contain multiple words (e.g. " Select all.").
+        // We split by space to ensure we assemble fragments and emit complete words singly.
+        let mut splits = transcript.split(' ');
+        
+        if let Some(first) = splits.next() {
+            if !first.is_empty() {
+                current_word.push_str(first);
             }
             
-            // Spawn task to forward partial transcripts to overlay
-            let app_partial = app.clone();
-            tokio::spawn(async move {
-                let noise_re = regex::Regex::new(r"\[.*?\]|\(.*?\)").unwrap();
-                let space_re = regex::Regex::new(r"\s+").unwrap();
-                while let Some(partial_text) = partial_rx.recv().await {
-                    let cleaned = noise_re.replace_all(&partial_text, " ");
-                    let cleaned = space_re.replace_all(&cleaned, " ").trim().to_string();
-                    
-                    if let Some(window) = app_partial.get_webview_window("main") {
-                        let _ = window.emit("streaming-partial-transcript", &cleaned);
-                    }
-                }
-            });
-            
-            // Spawn task to handle incoming committed transcripts
-            let app_clone = app.clone();
-            let session_id_clone = session_id.clone();
-            let sessions_clone = state.sessions.clone();
-            
-            let voice_cmds_enabled = voice_commands_enabled.unwrap_or(true);
-            tokio::spawn(async move {
-                let noise_re = regex::Regex::new(r"\[.*?\]|\(.*?\)").unwrap();
-                let space_re = regex::Regex::new(r"\s+").unwrap();
-                while let Some(raw_transcript) = transcript_rx.recv().await {
-                    let transcript = noise_re.replace_all(&raw_transcript, " ");
-                    let transcript = space_re.replace_all(&transcript, " ").trim().to_string();
-                    // Clear partial overlay when committed text arrives
-                    if let Some(window) = app_clone.get_webview_window("main") {
-                        let _ = window.emit("streaming-partial-clear", ());
-                    }
-                    
-                    // Apply formatting based on smart_format setting
-                    let formatted_transcript = if smart_format {
-                        transcript
-                    } else {
-                        normalize_whisper_transcript(&transcript)
-                    };
-                    
-                    // Apply word correction if custom words are configured
-                    let corrected_transcript = if let Ok(settings) = crate::commands::settings::get_settings(app_clone.clone()).await {
-                        if settings.word_correction_enabled {
-                            apply_word_correction_sync(&formatted_transcript, &settings.custom_words, settings.word_correction_threshold)
-                        } else {
-                            formatted_transcript.clone()
-                        }
-                    } else {
-                        formatted_transcript.clone()
-                    };
-                    
-                    // Process voice commands if enabled
-                    if voice_cmds_enabled {
-                        let voice_commands = VoiceCommands::new_with_language(&voice_lang);
-                        let processed = process_voice_commands(&corrected_transcript, &voice_commands);
-                        
-                        // Execute command actions
-                        for action in &processed.actions {
-                            if let Err(e) = execute_streaming_command_action(action, &app_clone).await {
-                                eprintln!("[Voice Commands] Failed to execute action: {}", e);
-                            }
-                        }
-                        
-                        // Insert remaining text
-                        let text_to_insert = if processed.remaining_text.is_empty() {
-                            processed.processed_text.clone()
-                        } else if processed.processed_text.is_empty() {
-                            if processed.had_key_action {
-                                processed.remaining_text.clone()
-                            } else {
-                                format!("{} ", processed.remaining_text)
-                            }
-                        } else {
-                            format!("{}{}", processed.remaining_text, processed.processed_text)
-                        };
-                        
-                        if !text_to_insert.is_empty() {
-                            let _ = insert_transcript_text(&text_to_insert, &insertion_mode, &app_clone).await;
-                        }
-                    } else {
-                        // No voice commands - insert directly with space
-                        let transcript_with_space = format!("{} ", corrected_transcript);
-                        let _ = insert_transcript_text(&transcript_with_space, &insertion_mode, &app_clone).await;
-                    }
-                    
-                    // Emit event to frontend for status update
-                    if let Some(window) = app_clone.get_webview_window("main") {
-                        let _ = window.emit("streaming-transcript", corrected_transcript);
-                    }
+            for part in splits {
+                if !current_word.is_empty() {
+                    let completed = std::mem::take(&mut current_word);
+                    let leading = current_word_has_leading_space;
+                    push_completed_word!(completed, leading);
                 }
                 
-                // Clean up session when done
-                let mut sessions = sessions_clone.lock().await;
-                sessions.remove(&session_id_clone);
-            });
-            
-            Ok(session_id)
+                current_word = part.to_string();
+                current_word_has_leading_space = true;
+            }
         }
-        _ => Err(format!("Unsupported streaming provider: {}", provider)),
+    }
+    
+    // Flush any remaining fragments and buffer on session end
+    if !current_word.is_empty() {
+        let word = std::mem::take(&mut current_word);
+        let leading = current_word_has_leading_space;
+        push_completed_word!(word, leading);
+    }
+    if !pending_buffer.is_empty() {
+        let buffer_text = std::mem::take(&mut pending_buffer);
+        let leading = pending_leading_space;
+        
+        flush_voxtral_text(
+            pipeline, &buffer_text, leading, &insertion_mode, &app_clone
+        ).await;
+    }
+}
+
+/// Report a provider error to the main window
+fn emit_stream_error(app: &AppHandle, session_id: &str, error: ProviderError) {
+    eprintln!("[Streaming] {} error: {}", session_id, error);
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit("streaming-error", StreamingErrorPayload {
+            session_id: session_id.to_string(),
+            error,
+        });
+    }
+}
+
+/// Tell the main window the provider socket dropped and a reconnect is pending
+fn emit_reconnecting(app: &AppHandle, session_id: &str, attempt: u32) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit("streaming-reconnecting", StreamingReconnectingPayload {
+            session_id: session_id.to_string(),
+            attempt,
+        });
+    }
+}
+
+/// Tell the main window the session is connected again
+fn emit_reconnected(app: &AppHandle, session_id: &str) {
+    emit_session_event(app, "streaming-reconnected", session_id);
+}
+
+/// Emit an event whose payload is just the session id
+fn emit_session_event(app: &AppHandle, event: &str, session_id: &str) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit(event, session_id);
     }
 }
 
@@ -692,9 +497,9 @@ pub async fn send_streaming_audio(
     let state = app.state::<StreamingState>();
     let sessions = state.sessions.lock().await;
     
-    if let Some(audio_tx) = sessions.get(&session_id) {
+    if let Some(sink) = sessions.get(&session_id) {
         // Send to transcription
-        audio_tx
+        sink
             .send(audio_data)
             .await
             .map_err(|e| format!("Failed to send audio: {}", e))?;
@@ -715,9 +520,9 @@ pub async fn stop_streaming_transcription(
     let state = app.state::<StreamingState>();
     let mut sessions = state.sessions.lock().await;
     
-    if let Some(audio_tx) = sessions.remove(&session_id) {
-        // Send empty data to signal close
-        let _ = audio_tx.send(vec![]).await;
+    if let Some(sink) = sessions.remove(&session_id) {
+        // Ask the provider to flush and close the stream
+        sink.close().await;
         
         Ok(())
     } else {
@@ -725,165 +530,55 @@ pub async fn stop_streaming_transcription(
     }
 }
 
-// Helper function to insert transcript text
-async fn insert_transcript_text(text: &str, insertion_mode: &str, app_handle: &AppHandle) -> Result<(), String> {
-    if insertion_mode == "typing" {
-        services::direct_typing::inject_text_native(text, app_handle)
-            .map_err(|e| e.to_string())
-    } else {
-        services::clipboard_paste::insert_text_via_clipboard(text, app_handle)
-            .map_err(|e| e.to_string())
-    }
-}
-
-// Helper function to normalize Whisper-based transcript (matches Electron app behavior)
-fn normalize_whisper_transcript(text: &str) -> String {
-    // Lowercase the text
-    let lower = text.to_lowercase();
-    
-    // Remove all punctuation characters except apostrophes (to preserve contractions like "don't" or "dell'auto")
-    let cleaned: String = lower
-        .chars()
-        .filter(|c| !matches!(c, '.' | ',' | '/' | '#' | '!' | '$' | '%' | '^' | '&' | '*' 
-                              | ';' | ':' | '{' | '}' | '=' | '_' | '~' | '(' 
-                              | ')' | '[' | ']' | '"' | '<' | '>' | '?' | '@' | '+' | '|' 
-                              | '\\' | '-'))
-        .collect();
-    
-    // Replace multiple spaces with single space and trim
-    cleaned.split_whitespace().collect::<Vec<&str>>().join(" ")
-}
-
-// Helper function to apply word correction to transcript
-fn apply_word_correction_sync(text: &str, custom_words: &[String], threshold: f64) -> String {
-    if custom_words.is_empty() {
-        return text.to_string();
-    }
-    services::word_correction::apply_custom_words(text, custom_words, threshold)
-}
-
-/// Execute a voice command action (for streaming)
-async fn execute_streaming_command_action(action: &CommandAction, app: &AppHandle) -> Result<(), String> {
-    match action {
-        CommandAction::KeyPress(key) => {
-            services::direct_typing::send_key_native(key, app)
-                .map_err(|e| e.to_string())
-        }
-        CommandAction::KeyCombo(modifier, key) => {
-            services::direct_typing::send_key_combo_native(modifier, key, app)
-                .map_err(|e| e.to_string())
-        }
-        CommandAction::DeleteLastWord => {
-            // Smart delete: if text is selected, delete the selection.
-            // If nothing is selected, fall back to Ctrl+Backspace to delete last word.
-            let app_clone = app.clone();
-            tokio::task::spawn_blocking(move || {
-                services::clipboard_paste::delete_selected_or_last_word(&app_clone)
-            })
-            .await
-            .map_err(|e| format!("Task join error: {}", e))?
-        }
-        CommandAction::Rewrite => {
-            // Emit event to trigger text rewrite - frontend handles smart selection
-            if let Some(window) = app.get_webview_window("main") {
-                tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
-                let _ = window.emit("sparkle-trigger", ());
-            } else {
-                eprintln!("[Voice Commands] Main window not found for rewrite trigger");
-            }
-            Ok(())
-        }
-        CommandAction::PauseDictation => {
-            // Emit event to pause/stop dictation
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.emit("toggle-recording", ());
-            }
-            Ok(())
-        }
-        CommandAction::InsertText(_) => {
-            // Text insertion is handled separately in the main flow
-            Ok(())
-        }
-    }
-}
-
-/// Flush buffered Voxtral text: apply formatting, word correction, voice commands, and insert.
+/// Flush buffered Voxtral text through the transcript pipeline, execute commands and insert.
 /// Returns true if a voice command was executed (used for post-command punctuation suppression).
 async fn flush_voxtral_text(
+    pipeline: &TranscriptPipeline,
     buffer_text: &str,
     has_leading_space: bool,
-    smart_format: bool,
     insertion_mode: &str,
     app: &AppHandle,
-    voice_commands: &Option<VoiceCommands>,
 ) -> bool {
-    // Apply formatting
-    let formatted = if smart_format {
-        buffer_text.to_string()
-    } else {
-        normalize_whisper_transcript(buffer_text)
-    };
-    
-    // Apply word correction
-    let corrected = apply_word_correction_if_needed(&formatted, app).await;
-    
-    // Process voice commands if available
-    let mut had_command = false;
-    if let Some(vc) = voice_commands {
-        let processed = process_voice_commands(&corrected, vc);
-        had_command = processed.had_any_command;
-        
-        // Execute command actions
-        for action in &processed.actions {
-            if let Err(e) = execute_streaming_command_action(action, app).await {
-                eprintln!("[Voice Commands] Failed to execute action: {}", e);
-            }
-        }
-        
-        // Build text to insert from remaining + processed
-        let text_to_insert = if processed.remaining_text.is_empty() {
-            processed.processed_text.clone()
-        } else if processed.processed_text.is_empty() {
-            processed.remaining_text.clone()
-        } else {
-            format!("{}{}", processed.remaining_text, processed.processed_text)
-        };
-        
-        if !text_to_insert.is_empty() {
-            let final_text = if has_leading_space && !text_to_insert.starts_with(' ') {
-                format!(" {}", text_to_insert)
-            } else {
-                text_to_insert
-            };
-            let _ = insert_transcript_text(&final_text, insertion_mode, app).await;
-        }
-    } else {
-        // No voice commands - insert directly
-        let final_text = if has_leading_space && !corrected.starts_with(' ') {
-            format!(" {}", corrected)
-        } else {
-            corrected.clone()
-        };
-        let _ = insert_transcript_text(&final_text, insertion_mode, app).await;
+    let output = pipeline.process_words(buffer_text, has_leading_space);
+    apply_pipeline_output(&output, insertion_mode, app).await;
+    output.had_command
+}
+
+/// Insert the text and execute the actions of a pipeline output in order, and report the transcript
+async fn apply_pipeline_output(output: &PipelineOutput, insertion_mode: &str, app: &AppHandle) {
+    if let Err(e) = command_executor::execute_items(&output.items, insertion_mode, app).await {
+        eprintln!("[Streaming] {}", e);
     }
     
-    // Emit event to frontend
-    if let Some(window) = app.get_webview_window("main") {
-        let _ = window.emit("streaming-transcript", corrected);
+    // Emit event to frontend for status update
+    if !output.transcript.is_empty() {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.emit("streaming-transcript", &output.transcript);
+        }
     }
-    
-    had_command
 }
 
-/// Apply word correction using app settings (async helper for Voxtral buffer)
-async fn apply_word_correction_if_needed(text: &str, app: &AppHandle) -> String {
-    if let Ok(settings) = crate::commands::settings::get_settings(app.clone()).await {
-        if settings.word_correction_enabled {
-            apply_word_correction_sync(text, &settings.custom_words, settings.word_correction_threshold)
-        } else {
-            text.to_string()
+/// Build the transcript pipeline for a session from the current settings
+async fn build_pipeline(app: &AppHandle, options: &SessionOptions) -> TranscriptPipeline {
+    let settings = crate::commands::settings::get_settings(app.clone()).await.ok();
+    let (custom_commands, macro_variables) = settings
+        .as_ref()
+        .map(|s| (s.advanced.custom_voice_commands.clone(), s.advanced.macro_variables.clone()))
+        .unwrap_or_default();
+    let (custom_words, word_correction_threshold) = match settings {
+        Some(settings) if settings.word_correction_enabled => {
+            (settings.custom_words, settings.word_correction_threshold)
         }
-    } else {
-        text.to_string()
-    }
+        _ => (Vec::new(), 0.0),
+    };
+    
+    TranscriptPipeline::new(PipelineOptions {
+        smart_format: options.smart_format,
+        custom_words,
+        word_correction_threshold,
+        voice_commands: options.voice_commands_enabled.then(|| {
+            VoiceCommands::new_with_language(&options.voice_lang, &app.state::<CommandPacks>(), &custom_commands)
+                .with_variables(macro_variables)
+        }),
+    })
 }
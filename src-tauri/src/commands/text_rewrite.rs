@@ -1,68 +1,101 @@
-use crate::providers;
+use crate::providers::fallback::{self, AttemptKind, AttemptStatus, ProviderAttempt};
+use crate::providers::{chat, ProviderError, RewriteRegistry, RewriteRequest};
+use crate::providers::registry::RewriteProviderInfo;
 use tauri::AppHandle;
 
+/// List the built-in rewrite providers and their default models
+#[tauri::command]
+pub fn list_rewrite_providers() -> Vec<RewriteProviderInfo> {
+    RewriteRegistry::builtin().list()
+}
+
+/// List the models served by an OpenAI-compatible endpoint (`GET {base_url}/models`)
+#[tauri::command]
+pub async fn list_custom_rewrite_models(base_url: String, api_key: String) -> Result<Vec<String>, String> {
+    if base_url.trim().is_empty() {
+        return Err("Custom endpoint URL is not set".to_string());
+    }
+
+    chat::list_models(&base_url, &api_key)
+        .await
+        .map_err(|e| format!("Failed to list models: {}", e))
+}
+
 /// Rewrite text using the selected provider and prompting instructions
-/// This command loads the provider selection and API key from settings
+/// This command loads the provider selection, API key and model overrides from settings,
+/// and tries `AdvancedSettings.rewrite_fallbacks` in order when the selected provider fails
 #[tauri::command]
-pub async fn rewrite_text(app: AppHandle, text: String, prompt: String, api_key: String) -> Result<String, String> {
+pub async fn rewrite_text(app: AppHandle, text: String, prompt: String, api_key: String) -> Result<String, ProviderError> {
     // Validate inputs
     if text.trim().is_empty() {
-        return Err("No text provided".to_string());
+        return Err("No text provided".into());
+    }
+    if prompt.trim().is_empty() {
+        return Err("Prompt is required".into());
     }
     
     // Load settings to get rewrite provider
-    let settings = crate::commands::settings::get_settings(app)
+    let settings = crate::commands::settings::get_settings(app.clone())
         .await
         .map_err(|e| format!("Failed to load settings: {}", e))?;
     
-    // Get selected rewrite provider (default: groq)
-    let provider = &settings.rewrite_provider;
-    
-    // Get the appropriate API key for the selected provider
-    let provider_api_key = match provider.as_str() {
-        "sambanova" => &settings.sambanova_api_key,
-        "fireworks" => &settings.fireworks_api_key,
-        "gemini-flash" | "gemini-flash-lite" => &settings.gemini_api_key,
-        "mistral" => &settings.mistral_api_key,
-        "inception" => &settings.inception_api_key,
-        _ => &settings.groq_api_key,  // Default to groq
-    };
-    
-    // Use the provider's key if available, otherwise fall back to the passed key (for backward compatibility)
-    let active_key = if !provider_api_key.trim().is_empty() {
-        provider_api_key
-    } else {
-        &api_key
-    };
-    
-    if active_key.trim().is_empty() {
-        return Err("API key is not set".to_string());
-    }
-    
-    // Route to the selected provider
-    let result = match provider.as_str() {
-        "sambanova" => providers::sambanova::rewrite_text(text, prompt, active_key.to_string()).await,
-        "fireworks" => providers::fireworks::rewrite_text(text, prompt, active_key.to_string()).await,
-        "gemini-flash-lite" => providers::gemini::rewrite_text(text, prompt, active_key.to_string(), "gemini-3.1-flash-lite-preview".to_string()).await,
-        "gemini-flash" => providers::gemini::rewrite_text(text, prompt, active_key.to_string(), "gemini-3-flash-preview".to_string()).await,
-        "mistral" => providers::mistral::rewrite_text(text, prompt, active_key.to_string()).await,
-        "inception" => providers::inception::rewrite_text(text, prompt, active_key.to_string()).await,
-        _ => providers::groq::rewrite_text(text, prompt, active_key.to_string()).await,
-    };
-    
-    // Map errors to user-friendly messages
-    result.map_err(|e| {
-        let error_msg = e.to_string();
-        
-        // Check for specific error types
-        if error_msg.contains("rate limit") || error_msg.contains("429") {
-            "Rate limit exceeded. Please wait a moment.".to_string()
-        } else if error_msg.contains("network") || error_msg.contains("connection") || error_msg.contains("Connection failed") {
-            "Network error. Check your connection.".to_string()
-        } else if error_msg.contains("401") || error_msg.contains("unauthorized") {
-            "Invalid API key.".to_string()
+    // Try the selected rewrite provider (unknown ids fall back to groq), then the configured fallbacks
+    let registry = RewriteRegistry::from_settings(&settings);
+    let chain = fallback::fallback_chain(&settings.rewrite_provider, &settings.advanced.rewrite_fallbacks);
+
+    let mut last_error = ProviderError::Other("No rewrite provider available".to_string());
+    for (index, id) in chain.iter().enumerate() {
+        let provider = if index == 0 { registry.resolve(id) } else { registry.get(id) };
+        let Some(provider) = provider else {
+            continue;
+        };
+        let attempt = ProviderAttempt::new(AttemptKind::Rewrite, provider.id(), index + 1, chain.len());
+
+        // Use the provider's key if available, otherwise fall back to the passed key (for backward compatibility)
+        let provider_api_key = provider.api_key(&settings);
+        let active_key = if !provider_api_key.trim().is_empty() {
+            provider_api_key
+        } else if index == 0 {
+            &api_key
         } else {
-            format!("Text rewrite failed: {}", error_msg)
+            ""
+        };
+
+        if provider.requires_api_key() && active_key.trim().is_empty() {
+            last_error = ProviderError::Auth("API key is not set".to_string());
+            attempt.report(&app, AttemptStatus::Skipped, Some(&last_error));
+            continue;
         }
-    })
+
+        // Apply per-provider model overrides from settings
+        let model = match settings.advanced.rewrite_models.get(provider.id()) {
+            Some(overrides) => provider.default_model().with_overrides(overrides),
+            None => provider.default_model(),
+        };
+
+        attempt.report(&app, AttemptStatus::Started, None);
+        let result = provider
+            .rewrite(RewriteRequest {
+                text: text.clone(),
+                prompt: prompt.clone(),
+                api_key: active_key.to_string(),
+                model,
+                retry: settings.advanced.retry_policy.clone(),
+            })
+            .await;
+
+        match result {
+            Ok(rewritten) => {
+                attempt.report(&app, AttemptStatus::Succeeded, None);
+                return Ok(rewritten);
+            }
+            Err(e) => {
+                eprintln!("[Rewrite] {} failed: {}", provider.id(), e);
+                attempt.report(&app, AttemptStatus::Failed, Some(&e));
+                last_error = e;
+            }
+        }
+    }
+
+    Err(last_error)
 }
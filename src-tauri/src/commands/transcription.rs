@@ -1,156 +1,504 @@
-use crate::providers;
+use crate::providers::registry::{ProviderInfo, DEFAULT_TRANSCRIPTION_PROVIDER};
+use crate::providers::fallback::{self, AttemptKind, AttemptStatus, ProviderAttempt};
+use crate::providers::hedge;
+use crate::providers::{vocabulary, ProviderError, Transcript, TranscriptionProvider, TranscriptionRegistry, TranscriptionRequest};
 use crate::services;
-use crate::voice_commands::{VoiceCommands, process_voice_commands, CommandAction};
+use crate::services::segment_context::SegmentContexts;
+use crate::services::segment_sequencer::SegmentSequencer;
+use crate::services::offline_queue::{self, OfflineQueue};
+use crate::services::transcript_history::{self, HistoryEntry};
+use crate::commands::settings::Settings;
+use crate::services::command_executor;
+use crate::voice_commands::{interpret, CommandPacks, Item, VoiceCommands};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
 use tauri::{AppHandle, Manager, Emitter};
 
+/// List the registered batch transcription providers with their capabilities
+#[tauri::command]
+pub async fn list_transcription_providers(app: AppHandle) -> Result<Vec<ProviderInfo>, String> {
+    let settings = crate::commands::settings::get_settings(app.clone())
+        .await
+        .unwrap_or_default();
+    Ok(TranscriptionRegistry::for_app(&app, &settings).list())
+}
+
+/// How a segment is transcribed and where it belongs in its recording
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SegmentOptions {
+    pub language: Option<String>,
+    pub text_formatted: Option<bool>,
+    pub voice_commands_enabled: Option<bool>,
+    /// VAD session the segment belongs to
+    pub session_id: Option<String>,
+    /// Segment number within the session
+    pub sequence: Option<u64>,
+}
+
 /// Transcribe audio segment and insert text immediately
-/// Supports multiple providers (Groq, SambaNova) with batch audio processing
+/// Routes to any provider in the transcription registry with batch audio processing,
+/// retrying the same audio on `AdvancedSettings.transcription_fallbacks` when a provider fails.
+/// Returns the provider's segments and word timings, with `text` replaced by the formatted text.
+/// With a `session_id`, the end of the session's previous segments is sent as context,
+/// and with a `sequence` the text is inserted only after every earlier segment of the session.
 #[tauri::command]
 pub async fn transcribe_audio_segment(
     app: AppHandle,
     audio_data: Vec<u8>,
     api_key: String,
     insertion_mode: String,
-    language: Option<String>,
-    text_formatted: Option<bool>,
     api_service: Option<String>,
-    voice_commands_enabled: Option<bool>,
-) -> Result<String, String> {
+    options: SegmentOptions,
+) -> Result<Transcript, ProviderError> {
+    let SegmentOptions {
+        language,
+        text_formatted,
+        voice_commands_enabled,
+        session_id,
+        sequence,
+    } = options;
+
     // Validate inputs
     if audio_data.is_empty() {
-        return Err("No audio data provided".to_string());
-    }
-    
-    if api_key.trim().is_empty() {
-        return Err("API key is not set".to_string());
+        return Err(ProviderError::BadAudio("No audio data provided".to_string()));
     }
     
+    // Load settings once for the custom endpoint, key lookup and word correction
+    let settings = crate::commands::settings::get_settings(app.clone()).await.ok();
+
+    // Take this segment's place in the recording's insertion order; the ticket is
+    // released on every return path, so a failed segment never blocks later ones
+    let ticket = match (session_id.as_deref(), sequence) {
+        (Some(id), Some(sequence)) => {
+            let max_parallel = settings.as_ref().map_or(3, |s| s.advanced.max_parallel_segments);
+            Some(app.state::<SegmentSequencer>().arrive(id, sequence, max_parallel))
+        }
+        _ => None,
+    };
+    let slot = match &ticket {
+        Some(ticket) => ticket.transcription_slot().await,
+        None => None,
+    };
+
+    let context_enabled = settings.as_ref().is_none_or(|s| s.segment_context_enabled);
+    let context_session = session_id.filter(|_| context_enabled);
+    let context = context_session
+        .as_deref()
+        .and_then(|id| app.state::<SegmentContexts>().get(id));
+
     // Normalize language: 'multilingual' or empty -> None (auto-detect)
-    let normalized_lang = match language.as_deref() {
+    let requested_lang = match language.as_deref() {
         None => None,
         Some("") => None,
         Some("multilingual") => None,
         Some(code) => Some(code.to_string()),
     };
 
-    // Route to selected provider (default: Groq)
-    let service = api_service.unwrap_or_else(|| "groq".to_string());
-    let result: Result<String, String> = match service.as_str() {
-        "sambanova" => providers::sambanova::transcribe_verbose(audio_data, api_key, normalized_lang)
-            .await
-            .map_err(|e| e.to_string()),
-        "fireworks" => providers::fireworks::transcribe_verbose(audio_data, api_key, normalized_lang)
-            .await
-            .map_err(|e| e.to_string()),
-        "gemini" => providers::gemini::transcribe_verbose(audio_data, api_key, normalized_lang)
-            .await
-            .map_err(|e| e.to_string()),
-        "mistral" => providers::mistral::transcribe_verbose(audio_data, api_key, normalized_lang)
-            .await
-            .map_err(|e| e.to_string()),
-        _ => providers::groq::transcribe_verbose(audio_data, api_key, normalized_lang)
-            .await
-            .map_err(|e| e.to_string()),
-    };
+    // Route to selected provider (default: Groq), then to the configured fallbacks
+    let service = api_service.unwrap_or_else(|| DEFAULT_TRANSCRIPTION_PROVIDER.to_string());
+    let result = transcribe_with_fallbacks(
+        &app,
+        settings.as_ref(),
+        &service,
+        &api_key,
+        &audio_data,
+        requested_lang.as_deref(),
+        context,
+    )
+    .await;
+    drop(slot);
 
-    let text = result
-        .map_err(|error_msg| {
-            
-            // Check for specific error types
-            if error_msg.contains("rate limit") || error_msg.contains("429") {
-                "Rate limit exceeded. Please wait a moment.".to_string()
-            } else if error_msg.contains("network") || error_msg.contains("connection") || error_msg.contains("Connection failed") {
-                "Network error. Check your connection.".to_string()
-            } else if error_msg.contains("401") || error_msg.contains("unauthorized") {
-                "Invalid API key.".to_string()
-            } else {
-                format!("Transcription failed: {}", error_msg)
-            }
-        })?;
+    let mut transcript = match result {
+        Ok(transcript) => {
+            // The provider is reachable again; retry anything queued while offline
+            app.state::<OfflineQueue>().wake();
+            transcript
+        }
+        Err(e) if offline_queue::is_offline_error(&e) && settings.as_ref().is_none_or(|s| s.advanced.offline_queue_enabled) => {
+            // Keep the audio and recover its text once the connection is back
+            let queued = app.state::<OfflineQueue>().enqueue(
+                &app,
+                &service,
+                requested_lang.clone(),
+                text_formatted.unwrap_or(true),
+                voice_commands_enabled.unwrap_or(true),
+                &audio_data,
+            );
+            return match queued {
+                Ok(segment) => {
+                    eprintln!("[Offline Queue] Saved segment {} after: {}", segment.id, e);
+                    let _ = app.emit("segment-queued-offline", segment.id);
+                    Ok(Transcript::default())
+                }
+                Err(queue_error) => {
+                    eprintln!("[Offline Queue] Failed to save segment: {}", queue_error);
+                    Err(e)
+                }
+            };
+        }
+        Err(e) => return Err(e),
+    };
     
     // Skip empty transcriptions
+    if transcript.is_empty() {
+        transcript.text.clear();
+        return Ok(transcript);
+    }
+    
+    // Drop silence hallucinations ("Thank you for watching", credits, repetition loops)
+    let text = filter_hallucinations(&transcript, requested_lang.as_deref(), settings.as_ref());
     if text.trim().is_empty() {
-        return Ok(String::new());
+        transcript.text.clear();
+        return Ok(transcript);
     }
     
-    // Format text based on text_formatted setting
-    let preserve_formatting = text_formatted.unwrap_or(true);  // Default true
-    let mut formatted = if preserve_formatting {
-        format_whisper_transcript(&text)
-    } else {
-        normalize_whisper_transcript(&text)
-    };
+    // Format and apply word correction
+    let formatted = format_text(&text, text_formatted.unwrap_or(true), settings.as_ref());
     
-    // Apply word correction if custom words are configured
-    // Load settings to get custom words and threshold
-    if let Ok(settings) = crate::commands::settings::get_settings(app.clone()).await {
-        if settings.word_correction_enabled && !settings.custom_words.is_empty() {
-            formatted = services::word_correction::apply_custom_words(
-                &formatted,
-                &settings.custom_words,
-                settings.word_correction_threshold,
-            );
+    // Earlier segments of this recording are inserted first
+    if let Some(ticket) = &ticket {
+        ticket.wait_turn().await;
+    }
+
+    // Remember what was said for the next segment of this recording
+    if let Some(id) = &context_session {
+        if ticket.as_ref().is_none_or(|ticket| ticket.is_open()) {
+            let max_chars = settings
+                .as_ref()
+                .map_or(200, |s| s.advanced.segment_context_max_chars);
+            app.state::<SegmentContexts>().commit(id, &text, max_chars);
         }
     }
     
-    // Process voice commands if enabled
+    // Process voice commands if enabled: text and actions run in spoken order
     let voice_cmds_enabled = voice_commands_enabled.unwrap_or(true);
-    if voice_cmds_enabled {
+    let items = if voice_cmds_enabled {
         let lang = language.as_deref().unwrap_or("en");
-        let voice_commands = VoiceCommands::new_with_language(lang);
-        let processed = process_voice_commands(&formatted, &voice_commands);
-        
-        // Execute command actions first
-        for action in &processed.actions {
-            if let Err(e) = execute_command_action(action, &app).await {
-                eprintln!("[Voice Commands] Failed to execute action: {}", e);
-            }
+        let (custom, variables) = settings
+            .as_ref()
+            .map(|s| (s.advanced.custom_voice_commands.clone(), s.advanced.macro_variables.clone()))
+            .unwrap_or_default();
+        let voice_commands = VoiceCommands::new_with_language(lang, &app.state::<CommandPacks>(), &custom)
+            .with_variables(variables);
+        let mut interpretation = interpret(&formatted, &voice_commands);
+        interpretation.add_trailing_space();
+        interpretation.items
+    } else if formatted.is_empty() {
+        Vec::new()
+    } else {
+        // No voice commands - insert text directly, with a trailing space for dictation
+        vec![Item::Text(format!("{} ", formatted))]
+    };
+    command_executor::execute_items(&items, &insertion_mode, &app).await?;
+    
+    transcript.text = formatted;
+    Ok(transcript)
+}
+
+/// Transcripts recovered from the offline queue, oldest first
+#[tauri::command]
+pub fn get_transcript_history(app: AppHandle) -> Vec<HistoryEntry> {
+    transcript_history::load(&app)
+}
+
+/// Retry segments queued while offline, for the lifetime of the app.
+/// Recovered text goes to the transcript history and the clipboard instead of
+/// being typed into whatever window has focus by then.
+pub async fn recover_offline_segments(app: AppHandle) {
+    let mut failures = 0;
+    loop {
+        if recover_pending_segments(&app).await {
+            failures += 1;
+        } else {
+            failures = 0;
         }
-        
-        // Insert remaining text
-        let text_to_insert = if processed.remaining_text.is_empty() {
-            processed.processed_text.clone()
-        } else if processed.processed_text.is_empty() {
-            if processed.had_key_action {
-                processed.remaining_text.clone()
-            } else {
-                format!("{} ", processed.remaining_text)
+        app.state::<OfflineQueue>()
+            .wait(offline_queue::retry_delay(failures))
+            .await;
+    }
+}
+
+/// One pass over the queue, oldest first. Returns true if the network is still down.
+async fn recover_pending_segments(app: &AppHandle) -> bool {
+    let queue = app.state::<OfflineQueue>();
+    let pending = queue.pending(app);
+    if pending.is_empty() {
+        return false;
+    }
+
+    let settings = crate::commands::settings::get_settings(app.clone()).await.ok();
+    let mut recovered = Vec::new();
+    let mut offline = false;
+    for mut segment in pending {
+        let audio = match queue.audio(app, &segment) {
+            Ok(audio) => audio,
+            Err(e) => {
+                eprintln!("[Offline Queue] Dropping segment {} without audio: {}", segment.id, e);
+                queue.remove(app, &segment);
+                continue;
             }
-        } else {
-            format!("{}{}", processed.remaining_text, processed.processed_text)
         };
-        
-        if !text_to_insert.is_empty() {
-            match insertion_mode.as_str() {
-                "typing" => {
-                    services::direct_typing::inject_text_native(&text_to_insert, &app)
-                        .map_err(|e| format!("Failed to insert text: {}", e))?;
+
+        let language = segment.language.as_deref();
+        match transcribe_with_fallbacks(app, settings.as_ref(), &segment.provider, "", &audio, language, None).await {
+            Ok(transcript) => {
+                let text = filter_hallucinations(&transcript, language, settings.as_ref());
+                let text = format_text(&text, segment.text_formatted, settings.as_ref());
+                let text = if segment.voice_commands {
+                    recovered_text(app, &text, language, settings.as_ref())
+                } else {
+                    text
+                };
+                if !text.trim().is_empty() {
+                    recovered.push(HistoryEntry {
+                        text: text.trim().to_string(),
+                        recorded_at: segment.recorded_at,
+                        recovered: true,
+                    });
                 }
-                "clipboard" | _ => {
-                    services::clipboard_paste::insert_text_via_clipboard(&text_to_insert, &app)
-                        .map_err(|e| format!("Failed to insert text: {}", e))?;
+                queue.remove(app, &segment);
+            }
+            Err(e) if offline_queue::is_offline_error(&e) => {
+                offline = true;
+                break;
+            }
+            Err(e) => {
+                segment.attempts += 1;
+                if segment.attempts >= offline_queue::MAX_ATTEMPTS {
+                    eprintln!("[Offline Queue] Dropping segment {} after {} failures: {}", segment.id, segment.attempts, e);
+                    queue.remove(app, &segment);
+                } else if let Err(e) = queue.update(app, &segment) {
+                    eprintln!("[Offline Queue] Failed to update segment {}: {}", segment.id, e);
                 }
             }
         }
-    } else {
-        // No voice commands - insert text directly
-        if !formatted.is_empty() {
-            // Append trailing space as expected for dictation
-            let text_to_insert = format!("{} ", formatted);
-            match insertion_mode.as_str() {
-                "typing" => {
-                    services::direct_typing::inject_text_native(&text_to_insert, &app)
-                        .map_err(|e| format!("Failed to insert text: {}", e))?;
-                }
-                "clipboard" | _ => {
-                    services::clipboard_paste::insert_text_via_clipboard(&text_to_insert, &app)
-                        .map_err(|e| format!("Failed to insert text: {}", e))?;
+    }
+
+    if !recovered.is_empty() {
+        deliver_recovered(app, recovered);
+    }
+    offline
+}
+
+/// Recovered text with spoken punctuation applied like the live path. Key and other
+/// command actions are dropped: the text goes to the clipboard, not a focused window.
+fn recovered_text(app: &AppHandle, text: &str, language: Option<&str>, settings: Option<&Settings>) -> String {
+    let custom = settings
+        .map(|s| s.advanced.custom_voice_commands.clone())
+        .unwrap_or_default();
+    let voice_commands = VoiceCommands::new_with_language(
+        language.unwrap_or("en"),
+        &app.state::<CommandPacks>(),
+        &custom,
+    );
+    interpret(text, &voice_commands).text_only()
+}
+
+/// Save recovered text to the history and put it on the clipboard
+fn deliver_recovered(app: &AppHandle, entries: Vec<HistoryEntry>) {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    let count = entries.len();
+    let text = entries
+        .iter()
+        .map(|entry| entry.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if let Err(e) = transcript_history::append(app, entries) {
+        eprintln!("[Offline Queue] Failed to save transcript history: {}", e);
+    }
+    if let Err(e) = app.clipboard().write_text(text) {
+        eprintln!("[Offline Queue] Failed to copy recovered text: {}", e);
+    }
+    println!("[Offline Queue] Recovered {} segment(s)", count);
+    let _ = app.emit("offline-segments-recovered", count);
+}
+
+/// Transcribe audio with `service`, then with `AdvancedSettings.transcription_fallbacks`.
+/// `api_key` is used for `service` when set; every other provider uses its stored key.
+async fn transcribe_with_fallbacks(
+    app: &AppHandle,
+    settings: Option<&Settings>,
+    service: &str,
+    api_key: &str,
+    audio_data: &[u8],
+    requested_lang: Option<&str>,
+    context: Option<String>,
+) -> Result<Transcript, ProviderError> {
+    let registry = match settings {
+        Some(settings) => TranscriptionRegistry::for_app(app, settings),
+        None => TranscriptionRegistry::builtin(),
+    };
+    let fallbacks = settings
+        .map(|s| s.advanced.transcription_fallbacks.as_slice())
+        .unwrap_or_default();
+    let chain = fallback::fallback_chain(service, fallbacks);
+    let vocabulary = settings.map(vocabulary::from_settings).unwrap_or_default();
+
+    let mut transcript = None;
+    let mut last_error = ProviderError::Other(format!("Unknown transcription provider: {}", service));
+    for (index, id) in chain.iter().enumerate() {
+        // Unknown selected ids fall back to the default provider; unknown fallbacks are ignored
+        let provider = if index == 0 { registry.resolve(id) } else { registry.get(id) };
+        let Some(provider) = provider else {
+            continue;
+        };
+        let attempt = ProviderAttempt::new(AttemptKind::Transcription, provider.id(), index + 1, chain.len());
+        let capabilities = provider.capabilities();
+
+        if audio_data.len() > capabilities.max_upload_bytes {
+            last_error = ProviderError::BadAudio(format!("Audio segment is too large for {}", provider.display_name()));
+            attempt.report(app, AttemptStatus::Skipped, Some(&last_error));
+            continue;
+        }
+
+        // The frontend passes the key of the selected provider; fallbacks use their stored key
+        let api_key = if index == 0 && !api_key.trim().is_empty() {
+            api_key.to_string()
+        } else {
+            settings
+                .map(|s| provider.api_key(s).to_string())
+                .unwrap_or_default()
+        };
+
+        if provider.requires_api_key() && api_key.trim().is_empty() {
+            last_error = ProviderError::Auth("API key is not set".to_string());
+            attempt.report(app, AttemptStatus::Skipped, Some(&last_error));
+            continue;
+        }
+
+        // Hints the provider cannot use are dropped so it auto-detects instead
+        let normalized_lang = requested_lang
+            .filter(|code| capabilities.supports_language(code))
+            .map(str::to_string);
+
+        let request = |api_key: String, language: Option<String>| TranscriptionRequest {
+            audio: audio_data.to_vec(),
+            api_key,
+            language,
+            vocabulary: vocabulary.clone(),
+            context: context.clone(),
+            retry: settings
+                .map(|s| s.advanced.retry_policy.clone())
+                .unwrap_or_default(),
+        };
+
+        attempt.report(app, AttemptStatus::Started, None);
+        let hedge = if index == 0 {
+            hedge_target(&registry, settings, provider.id(), audio_data.len())
+        } else {
+            None
+        };
+        let result = match hedge {
+            Some((secondary, secondary_key, delay)) => {
+                let secondary_lang = requested_lang
+                    .filter(|code| secondary.capabilities().supports_language(code))
+                    .map(str::to_string);
+                hedge::transcribe(
+                    provider.as_ref(),
+                    request(api_key, normalized_lang),
+                    secondary.as_ref(),
+                    request(secondary_key, secondary_lang),
+                    delay,
+                )
+                .await
+            }
+            None => provider
+                .transcribe(request(api_key, normalized_lang))
+                .await
+                .map(|transcript| (transcript, provider.id())),
+        };
+
+        match result {
+            Ok((result, winner)) => {
+                if winner == provider.id() {
+                    attempt.report(app, AttemptStatus::Succeeded, None);
+                } else {
+                    // The hedged request answered first; the primary request was cancelled
+                    eprintln!("[Hedging] {} answered before {}", winner, provider.id());
+                    attempt.report(app, AttemptStatus::Superseded, None);
+                    ProviderAttempt::new(AttemptKind::Transcription, winner, index + 1, chain.len())
+                        .report(app, AttemptStatus::Succeeded, None);
                 }
+                transcript = Some(result);
+                break;
+            }
+            Err(e) => {
+                eprintln!("[Transcription] {} failed: {}", provider.id(), e);
+                attempt.report(app, AttemptStatus::Failed, Some(&e));
+                last_error = e;
             }
         }
     }
-    
-    Ok(formatted)
+
+    transcript.ok_or(last_error)
+}
+
+/// Secondary provider, its key and the hedging delay for requests to `primary`,
+/// when hedging is enabled and the secondary can take this segment
+fn hedge_target(
+    registry: &TranscriptionRegistry,
+    settings: Option<&Settings>,
+    primary: &str,
+    audio_len: usize,
+) -> Option<(Arc<dyn TranscriptionProvider>, String, Duration)> {
+    let settings = settings?;
+    let hedging = &settings.advanced.hedging;
+    if !hedging.enabled {
+        return None;
+    }
+    let secondary = registry
+        .get(&hedging.provider)
+        .filter(|secondary| secondary.id() != primary)?;
+    if audio_len > secondary.capabilities().max_upload_bytes {
+        return None;
+    }
+    let api_key = secondary.api_key(settings).to_string();
+    if secondary.requires_api_key() && api_key.trim().is_empty() {
+        return None;
+    }
+    Some((secondary, api_key, Duration::from_millis(hedging.delay_ms)))
+}
+
+/// Transcript text without silence hallucinations (logged when something was removed)
+fn filter_hallucinations(transcript: &Transcript, language: Option<&str>, settings: Option<&Settings>) -> String {
+    let filter_settings = settings
+        .map(|s| s.advanced.hallucination_filter.clone())
+        .unwrap_or_default();
+    let text = services::hallucination_filter::filter_transcript(transcript, language, &filter_settings);
+    if text.trim() != transcript.text.trim() {
+        eprintln!("[Transcription] Hallucination filter: {:?} -> {:?}", transcript.text.trim(), text.trim());
+    }
+    text
+}
+
+/// Format text based on the text_formatted setting, then apply word correction
+/// if custom words are configured
+fn format_text(text: &str, preserve_formatting: bool, settings: Option<&Settings>) -> String {
+    let formatted = if preserve_formatting {
+        format_whisper_transcript(text)
+    } else {
+        normalize_whisper_transcript(text)
+    };
+
+    match settings {
+        Some(settings) if settings.word_correction_enabled && !settings.custom_words.is_empty() => {
+            services::word_correction::apply_custom_words(
+                &formatted,
+                &settings.custom_words,
+                settings.word_correction_threshold,
+            )
+        }
+        _ => formatted,
+    }
+}
+
+/// Render a transcript returned by `transcribe_audio_segment` as SubRip subtitles
+#[tauri::command]
+pub fn transcript_to_srt(transcript: Transcript) -> String {
+    transcript.to_srt()
 }
 
 /// Format Whisper transcript (preserve formatting, trim, add space)
@@ -181,41 +529,3 @@ fn normalize_whisper_transcript(text: &str) -> String {
     // Add trailing space for natural flow
     format!("{} ", cleaned.trim())
 }
-
-/// Execute a voice command action
-async fn execute_command_action(action: &CommandAction, app: &AppHandle) -> Result<(), String> {
-    match action {
-        CommandAction::KeyPress(key) => {
-            services::direct_typing::send_key_native(key, app)
-                .map_err(|e| e.to_string())
-        }
-        CommandAction::KeyCombo(modifier, key) => {
-            services::direct_typing::send_key_combo_native(modifier, key, app)
-                .map_err(|e| e.to_string())
-        }
-        CommandAction::DeleteLastWord => {
-            // Send Ctrl+Backspace to delete last word
-            services::direct_typing::send_key_combo_native("control", "backspace", app)
-                .map_err(|e| e.to_string())
-        }
-        CommandAction::Rewrite => {
-            // Emit event to trigger text rewrite - frontend handles smart selection
-            if let Some(window) = app.get_webview_window("main") {
-                tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
-                let _ = window.emit("sparkle-trigger", ());
-            }
-            Ok(())
-        }
-        CommandAction::PauseDictation => {
-            // Emit event to pause/stop dictation
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.emit("toggle-recording", ());
-            }
-            Ok(())
-        }
-        CommandAction::InsertText(_) => {
-            // Text insertion is handled separately in the main flow
-            Ok(())
-        }
-    }
-}
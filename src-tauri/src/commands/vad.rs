@@ -1,4 +1,6 @@
-use crate::vad::VadSessionManager;
+use crate::services::segment_context::SegmentContexts;
+use crate::services::segment_sequencer::SegmentSequencer;
+use crate::vad::{FinalSegment, VadSessionManager};
 use tauri::State;
 
 #[tauri::command]
@@ -26,7 +28,7 @@ pub async fn vad_push_frame(
 pub async fn vad_stop_session(
     session_id: String,
     state: State<'_, VadSessionManager>,
-) -> Result<Vec<u8>, String> {
+) -> Result<FinalSegment, String> {
     state.stop_session(session_id)
 }
 
@@ -34,6 +36,11 @@ pub async fn vad_stop_session(
 pub async fn vad_destroy_session(
     session_id: String,
     state: State<'_, VadSessionManager>,
+    contexts: State<'_, SegmentContexts>,
+    sequencer: State<'_, SegmentSequencer>,
 ) -> Result<(), String> {
+    // The recording is over; its transcript context is no longer needed
+    contexts.clear(&session_id);
+    sequencer.close(&session_id);
     state.destroy_session(session_id)
 }
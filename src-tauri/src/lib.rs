@@ -159,19 +159,38 @@ pub fn run() {
             Some(vec![]),
         ))
         .manage(StreamingState::default())
+        .manage(services::segment_context::SegmentContexts::default())
+        .manage(services::segment_sequencer::SegmentSequencer::default())
+        .manage(services::offline_queue::OfflineQueue::default())
         .manage(commands::settings::ReleaseState::default())
         .manage(QuitState(AtomicBool::new(false)))
         .manage(EnigoState(Mutex::new(Enigo::new(&EnigoSettings::default()).expect("Failed to init Enigo"))))
         .manage(commands::settings::TrayMenuAnchorState(Mutex::new(None)))
         .manage(commands::settings::TrayMenuSizeState(Mutex::new(None)))
+        .manage(commands::settings::PendingApiKeyFocus(Mutex::new(None)))
         .setup(|app| {
             // Initialize VAD session manager
             let vad_manager = vad::VadSessionManager::new(app.handle().clone());
             app.manage(vad_manager);
             
+            // Load voice command packs (built-in plus `<app data>/voice_commands/<tag>.json`)
+            let packs_dir = app.path().app_data_dir()?.join("voice_commands");
+            let _ = std::fs::create_dir_all(&packs_dir);
+            app.manage(voice_commands::CommandPacks::load(&packs_dir));
+
             // Register global shortcuts from settings
             register_shortcuts(app.handle());
 
+            // Pre-warm the streaming connection if enabled
+            let warm_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                commands::streaming::refresh_warm_connection(&warm_handle).await;
+            });
+
+            // Retry segments saved while offline (including ones left from a previous run)
+            let queue_handle = app.handle().clone();
+            tauri::async_runtime::spawn(commands::transcription::recover_offline_segments(queue_handle));
+
             // Pre-create tray menu window (hidden) so it's ready for first right-click
             let tray_menu_builder = tauri::WebviewWindowBuilder::new(
                 app,
@@ -550,17 +569,24 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             commands::transcribe_audio_segment,
+            commands::transcript_to_srt,
+            commands::get_transcript_history,
+            commands::list_transcription_providers,
             commands::insert_text,
             commands::select_all_text,
             commands::copy_selected_text,
             commands::clear_clipboard,
             commands::copy_selected_or_all_text,
             commands::rewrite_text,
+            commands::list_rewrite_providers,
+            commands::list_custom_rewrite_models,
             commands::get_settings,
             commands::save_settings,
             commands::reregister_shortcuts,
             commands::apply_theme,
             commands::open_settings_window,
+            commands::open_api_key_settings,
+            commands::take_pending_api_key_focus,
             commands::exit_app,
             commands::tray_menu_ready,
             commands::toggle_main_window,
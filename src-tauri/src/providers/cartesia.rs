@@ -1,7 +1,12 @@
+use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use serde::Deserialize;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
+use super::streaming::{SinkMessage, StreamChannels, StreamConfig, StreamEvent, StreamSession, StreamingProvider};
+use super::ProviderResult;
+use crate::commands::settings::Settings;
+
 // ============================================================================
 // Cartesia Streaming Structures
 // ============================================================================
@@ -12,112 +17,134 @@ struct CartesiaMessage {
     msg_type: String,
     text: Option<String>,
     is_final: Option<bool>,
+    #[serde(default, alias = "message")]
+    error: Option<String>,
 }
 
 // ============================================================================
 // Cartesia WebSocket Streaming
 // ============================================================================
 
-/// Start Cartesia streaming session
-/// Connects to Cartesia WebSocket and returns a channel for sending audio
-pub async fn start_streaming(
-    api_key: String,
-    language: Option<String>,
-) -> Result<(
-    tokio::sync::mpsc::Sender<Vec<u8>>,
-    tokio::sync::mpsc::Receiver<String>,
-    tokio::sync::mpsc::Receiver<String>,
-), Box<dyn std::error::Error + Send + Sync>> {
-    
-    // Build WebSocket URL with query parameters
-    let model = "ink-whisper";
-    let encoding = "pcm_s16le";
-    let sample_rate = 16000;
-    let version = "2025-04-16";
-    
-    // Build URL with required parameters
-    let mut url = format!(
-        "wss://api.cartesia.ai/stt/websocket?model={}&encoding={}&sample_rate={}&api_key={}&cartesia_version={}",
-        model,
-        encoding,
-        sample_rate,
-        urlencoding::encode(&api_key),
-        version
-    );
-    
-    // Add language if specified (omit for multilingual)
-    if let Some(lang) = language {
-        url.push_str(&format!("&language={}", lang));
+/// Cartesia Ink Whisper streaming transcription
+pub struct CartesiaProvider;
+
+#[async_trait]
+impl StreamingProvider for CartesiaProvider {
+    fn id(&self) -> &'static str {
+        "cartesia"
     }
-    
-    // Connect to WebSocket
-    let (ws_stream, _response) = connect_async(&url).await?;
-    
-    let (mut write, mut read) = ws_stream.split();
-    
-    // Create channels for communication
-    let (audio_tx, mut audio_rx) = tokio::sync::mpsc::channel::<Vec<u8>>(100);
-    let (transcript_tx, transcript_rx) = tokio::sync::mpsc::channel::<String>(100);
-    let (partial_tx, partial_rx) = tokio::sync::mpsc::channel::<String>(100);
-    
-    // Spawn task to send audio chunks to Cartesia
-    tokio::spawn(async move {
-        while let Some(audio_data) = audio_rx.recv().await {
-            if audio_data.is_empty() {
-                // Empty data means close connection - send finalize signal
-                let _ = write.send(Message::Text("finalize".to_string())).await;
-                
-                // Wait for final transcript processing
-                tokio::time::sleep(tokio::time::Duration::from_millis(800)).await;
-                
-                // Send done and close
-                let _ = write.send(Message::Text("done".to_string())).await;
-                let _ = write.send(Message::Close(None)).await;
-                break;
-            }
-            
-            if let Err(_) = write.send(Message::Binary(audio_data)).await {
-                break;
-            }
+
+    fn display_name(&self) -> &'static str {
+        "Cartesia"
+    }
+
+    fn api_key<'a>(&self, settings: &'a Settings) -> &'a str {
+        &settings.cartesia_api_key
+    }
+
+    /// Connects to Cartesia WebSocket and starts the send/receive tasks
+    async fn connect(&self, config: StreamConfig) -> ProviderResult<StreamSession> {
+        // Build WebSocket URL with query parameters
+        let model = "ink-whisper";
+        let encoding = "pcm_s16le";
+        let sample_rate = 16000;
+        let version = "2025-04-16";
+        
+        // Build URL with required parameters
+        let mut url = format!(
+            "wss://api.cartesia.ai/stt/websocket?model={}&encoding={}&sample_rate={}&api_key={}&cartesia_version={}",
+            model,
+            encoding,
+            sample_rate,
+            urlencoding::encode(&config.api_key),
+            version
+        );
+        
+        // Add language if specified (omit for multilingual)
+        if let Some(lang) = config.language {
+            url.push_str(&format!("&language={}", lang));
         }
-    });
-    
-    // Spawn task to receive transcripts from Cartesia
-    let transcript_tx_clone = transcript_tx.clone();
-    let partial_tx_clone = partial_tx.clone();
-    tokio::spawn(async move {
-        while let Some(msg) = read.next().await {
-            match msg {
-                Ok(Message::Text(text)) => {
-                    // Parse Cartesia message
-                    if let Ok(ct_msg) = serde_json::from_str::<CartesiaMessage>(&text) {
-                        // Check if this is a final transcript
-                        if ct_msg.msg_type == "transcript" {
-                            if let Some(transcript_text) = ct_msg.text {
-                                let transcript = transcript_text.trim();
-                                if !transcript.is_empty() {
-                                    if ct_msg.is_final.unwrap_or(false) {
-                                        if let Err(_) = transcript_tx_clone.send(transcript.to_string()).await {
-                                            break;
+        
+        // Connect to WebSocket
+        let (ws_stream, _response) = connect_async(&url).await?;
+        
+        let (mut write, mut read) = ws_stream.split();
+        
+        let (session, channels) = StreamSession::channel();
+        let StreamChannels { mut audio_rx, events_tx } = channels;
+        
+        // Spawn task to send audio chunks to Cartesia
+        let send_events_tx = events_tx.clone();
+        tokio::spawn(async move {
+            while let Some(message) = audio_rx.recv().await {
+                match message {
+                    SinkMessage::Audio(audio_data) => {
+                        if let Err(e) = write.send(Message::Binary(audio_data)).await {
+                            let _ = send_events_tx.send(StreamEvent::Error(e.into())).await;
+                            break;
+                        }
+                    }
+                    SinkMessage::KeepAlive => {
+                        if write.send(Message::Ping(Vec::new())).await.is_err() {
+                            break;
+                        }
+                    }
+                    SinkMessage::Close => {
+                        // Send finalize signal
+                        let _ = write.send(Message::Text("finalize".to_string())).await;
+                        
+                        // Wait for final transcript processing
+                        tokio::time::sleep(tokio::time::Duration::from_millis(800)).await;
+                        
+                        // Send done and close
+                        let _ = write.send(Message::Text("done".to_string())).await;
+                        let _ = write.send(Message::Close(None)).await;
+                        break;
+                    }
+                }
+            }
+        });
+        
+        // Spawn task to receive transcripts from Cartesia
+        tokio::spawn(async move {
+            while let Some(msg) = read.next().await {
+                match msg {
+                    Ok(Message::Text(text)) => {
+                        // Parse Cartesia message
+                        if let Ok(ct_msg) = serde_json::from_str::<CartesiaMessage>(&text) {
+                            if ct_msg.msg_type == "transcript" {
+                                if let Some(transcript_text) = ct_msg.text {
+                                    let transcript = transcript_text.trim();
+                                    if !transcript.is_empty() {
+                                        let event = if ct_msg.is_final.unwrap_or(false) {
+                                            StreamEvent::Final(transcript.to_string())
+                                        } else {
+                                            StreamEvent::Partial(transcript.to_string())
+                                        };
+                                        if events_tx.send(event).await.is_err() {
+                                            return;
                                         }
-                                    } else {
-                                        let _ = partial_tx_clone.send(transcript.to_string()).await;
                                     }
                                 }
+                            } else if ct_msg.msg_type == "error" {
+                                let message = ct_msg.error.unwrap_or_else(|| "Unknown error".to_string());
+                                let _ = events_tx.send(StreamEvent::Error(message.into())).await;
                             }
                         }
                     }
+                    Ok(Message::Close(_)) => {
+                        break;
+                    }
+                    Err(e) => {
+                        let _ = events_tx.send(StreamEvent::Error(e.into())).await;
+                        break;
+                    }
+                    _ => {}
                 }
-                Ok(Message::Close(_)) => {
-                    break;
-                }
-                Err(_) => {
-                    break;
-                }
-                _ => {}
             }
-        }
-    });
-    
-    Ok((audio_tx, transcript_rx, partial_rx))
+            let _ = events_tx.send(StreamEvent::Closed).await;
+        });
+        
+        Ok(session)
+    }
 }
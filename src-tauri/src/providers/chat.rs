@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{http, retry, ProviderResult, RewriteModel};
+use crate::commands::settings::RetrySettings;
+
+// ============================================================================
+// Chat Completion Structures (OpenAI-compatible)
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct ChatMessage {
+    pub role: String,
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn system(content: impl Into<String>) -> Self {
+        Self { role: "system".to_string(), content: content.into() }
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        Self { role: "user".to_string(), content: content.into() }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChatCompletionRequest {
+    pub model: String,
+    pub messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    pub stream: bool,
+    /// Provider-specific parameters (top_p, reasoning_effort, ...)
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl ChatCompletionRequest {
+    pub fn new(model: &RewriteModel, messages: Vec<ChatMessage>) -> Self {
+        Self {
+            model: model.model.clone(),
+            messages,
+            temperature: model.temperature,
+            max_tokens: model.max_tokens,
+            stream: false,
+            extra: Map::new(),
+        }
+    }
+
+    /// Add a provider-specific parameter
+    pub fn with(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.extra.insert(key.to_string(), value.into());
+        self
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    message: ChatMessageResponse,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatMessageResponse {
+    content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelEntry {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelListResponse {
+    data: Vec<ModelEntry>,
+}
+
+// ============================================================================
+// Endpoint URLs
+// ============================================================================
+
+/// Strip whitespace, trailing slashes and a trailing "/chat/completions" from a base URL
+fn api_base(base_url: &str) -> &str {
+    let base = base_url.trim().trim_end_matches('/');
+    base.strip_suffix("/chat/completions").unwrap_or(base)
+}
+
+/// Chat completions URL for a base URL such as "http://localhost:11434/v1"
+pub fn completions_url(base_url: &str) -> String {
+    format!("{}/chat/completions", api_base(base_url))
+}
+
+/// Model list URL for a base URL such as "http://localhost:11434/v1"
+pub fn models_url(base_url: &str) -> String {
+    format!("{}/models", api_base(base_url))
+}
+
+// ============================================================================
+// Chat Completion API
+// ============================================================================
+
+/// Attach Bearer auth unless the key is empty (local servers without authentication)
+fn with_auth(builder: reqwest::RequestBuilder, api_key: &str) -> reqwest::RequestBuilder {
+    if api_key.trim().is_empty() {
+        builder
+    } else {
+        builder.header("Authorization", format!("Bearer {}", api_key))
+    }
+}
+
+/// POST a chat completion with Bearer auth and return the first non-empty choice
+pub async fn complete(
+    url: &str,
+    api_key: &str,
+    request_body: &ChatCompletionRequest,
+    retry_policy: &RetrySettings,
+) -> ProviderResult<String> {
+    let response = retry::send(retry_policy, || {
+        Ok(with_auth(http::client().post(url), api_key)
+            .header("Content-Type", "application/json")
+            .header("Accept", "application/json")
+            .json(request_body))
+    })
+    .await?;
+
+    let result: ChatCompletionResponse = response.json().await?;
+
+    // Extract content from first choice
+    if let Some(content) = result.choices.first().and_then(|c| c.message.content.as_deref()) {
+        let content = content.trim();
+        if !content.is_empty() {
+            return Ok(content.to_string());
+        }
+    }
+
+    Err("No content in response".into())
+}
+
+/// GET the model ids served by an OpenAI-compatible endpoint, sorted by name
+pub async fn list_models(base_url: &str, api_key: &str) -> ProviderResult<Vec<String>> {
+    let response = http::send(
+        with_auth(http::client().get(models_url(base_url)), api_key)
+            .header("Accept", "application/json"),
+    )
+    .await?;
+
+    let result: ModelListResponse = response.json().await?;
+    let mut models: Vec<String> = result.data.into_iter().map(|m| m.id).collect();
+    models.sort();
+    Ok(models)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_urls_from_base() {
+        assert_eq!(completions_url("http://localhost:11434/v1/"), "http://localhost:11434/v1/chat/completions");
+        assert_eq!(models_url("http://localhost:11434/v1"), "http://localhost:11434/v1/models");
+    }
+
+    #[test]
+    fn test_urls_from_full_completions_url() {
+        let url = "http://127.0.0.1:8080/v1/chat/completions";
+        assert_eq!(completions_url(url), url);
+        assert_eq!(models_url(url), "http://127.0.0.1:8080/v1/models");
+    }
+}
@@ -0,0 +1,223 @@
+use async_trait::async_trait;
+use reqwest::multipart;
+use super::chat::{self, ChatCompletionRequest, ChatMessage};
+use super::{http, retry, vocabulary, ProviderCapabilities, ProviderError, ProviderResult, RewriteModel, RewriteProvider, RewriteRequest, Transcript, TranscriptionProvider, TranscriptionRequest, WHISPER_LANGUAGES};
+use super::transcript::VerboseJson;
+use crate::commands::settings::{CustomRewriteSettings, CustomTranscriptionSettings, Settings};
+
+/// Model sent when the user leaves the model name empty
+const DEFAULT_MODEL: &str = "whisper-1";
+
+/// Form fields set by the provider itself; extra fields cannot override them
+const RESERVED_FIELDS: &[&str] = &["file", "model", "response_format", "language"];
+
+// ============================================================================
+// Whisper Transcription API
+// ============================================================================
+
+/// Any server speaking the OpenAI `/audio/transcriptions` multipart protocol
+/// (faster-whisper-server, whisper.cpp, vLLM, LocalAI, ...)
+pub struct CustomProvider {
+    config: CustomTranscriptionSettings,
+}
+
+impl CustomProvider {
+    pub fn new(config: CustomTranscriptionSettings) -> Self {
+        Self { config }
+    }
+}
+
+/// Build the transcription URL from a base URL such as "http://localhost:8000/v1".
+/// A URL that already ends with the transcription path is used as is.
+fn transcription_url(base_url: &str) -> String {
+    let base = base_url.trim().trim_end_matches('/');
+    if base.ends_with("/audio/transcriptions") {
+        base.to_string()
+    } else {
+        format!("{}/audio/transcriptions", base)
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for CustomProvider {
+    fn id(&self) -> &'static str {
+        "custom"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Custom endpoint"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            languages: WHISPER_LANGUAGES,
+            max_upload_bytes: 25 * 1024 * 1024,
+            timestamps: false,
+        }
+    }
+
+    fn api_key<'a>(&self, settings: &'a Settings) -> &'a str {
+        &settings.custom_transcription.api_key
+    }
+
+    fn requires_api_key(&self) -> bool {
+        false
+    }
+
+    /// Transcribe audio using the configured OpenAI-compatible endpoint
+    async fn transcribe(&self, request: TranscriptionRequest) -> ProviderResult<Transcript> {
+        if self.config.base_url.trim().is_empty() {
+            return Err("Custom endpoint URL is not set".into());
+        }
+
+        // Validate audio data
+        if request.audio.len() < 100 {
+            return Err(ProviderError::BadAudio("Audio data too small".to_string()));
+        }
+
+        let model = match self.config.model.trim() {
+            "" => DEFAULT_MODEL.to_string(),
+            model => model.to_string(),
+        };
+
+        // Create multipart form (rebuilt for every retry attempt)
+        let build_form = || -> ProviderResult<multipart::Form> {
+            let part = multipart::Part::bytes(request.audio.clone())
+                .file_name("output.wav")
+                .mime_str("audio/wav")?;
+
+            let mut form = multipart::Form::new()
+                .part("file", part)
+                .text("model", model.clone())
+                .text("response_format", "json");
+
+            // Add language if specified
+            if let Some(lang) = &request.language {
+                form = form.text("language", lang.clone());
+            }
+
+            // Custom words and the previous segment, unless an extra field sets the prompt
+            let prompt_overridden = self.config.extra_fields.keys().any(|name| name.trim() == "prompt");
+            if !prompt_overridden {
+                if let Some(prompt) = vocabulary::whisper_prompt(&request.vocabulary, request.context.as_deref()) {
+                    form = form.text("prompt", prompt);
+                }
+            }
+
+            for (name, value) in &self.config.extra_fields {
+                let name = name.trim();
+                if !name.is_empty() && !RESERVED_FIELDS.contains(&name) {
+                    form = form.text(name.to_string(), value.clone());
+                }
+            }
+
+            Ok(form)
+        };
+
+        let url = transcription_url(&self.config.base_url);
+        let response = retry::send(&request.retry, || {
+            let builder = http::client().post(&url).multipart(build_form()?);
+
+            // Self-hosted servers usually run without authentication
+            Ok(if request.api_key.trim().is_empty() {
+                builder
+            } else {
+                builder.header("Authorization", format!("Bearer {}", request.api_key))
+            })
+        })
+        .await?;
+
+        // Plain `json` (the widest supported format) is a subset of `verbose_json`
+        let result: VerboseJson = response.json().await?;
+        Ok(result.into())
+    }
+}
+
+// ============================================================================
+// Chat Completion API
+// ============================================================================
+
+/// Rewrite text with any OpenAI-compatible chat completions server
+/// (Ollama, llama.cpp server, vLLM, LM Studio, ...)
+pub struct CustomRewriteProvider {
+    config: CustomRewriteSettings,
+}
+
+impl CustomRewriteProvider {
+    pub fn new(config: CustomRewriteSettings) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl RewriteProvider for CustomRewriteProvider {
+    fn id(&self) -> &'static str {
+        "custom"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Custom endpoint"
+    }
+
+    fn default_model(&self) -> RewriteModel {
+        RewriteModel::new(self.config.model.trim(), None, None)
+    }
+
+    fn api_key<'a>(&self, settings: &'a Settings) -> &'a str {
+        &settings.custom_rewrite_endpoint.api_key
+    }
+
+    fn requires_api_key(&self) -> bool {
+        false
+    }
+
+    async fn rewrite(&self, request: RewriteRequest) -> ProviderResult<String> {
+        if self.config.base_url.trim().is_empty() {
+            return Err("Custom endpoint URL is not set".into());
+        }
+        if request.model.model.is_empty() {
+            return Err("Custom endpoint model is not set".into());
+        }
+
+        let request_body = ChatCompletionRequest::new(
+            &request.model,
+            vec![
+                ChatMessage::system(request.prompt),
+                ChatMessage::user(request.text),
+            ],
+        );
+
+        chat::complete(
+            &chat::completions_url(&self.config.base_url),
+            &request.api_key,
+            &request_body,
+            &request.retry,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transcription_url_appends_path() {
+        assert_eq!(
+            transcription_url("http://localhost:8000/v1"),
+            "http://localhost:8000/v1/audio/transcriptions"
+        );
+        assert_eq!(
+            transcription_url(" http://localhost:8000/v1/ "),
+            "http://localhost:8000/v1/audio/transcriptions"
+        );
+    }
+
+    #[test]
+    fn test_transcription_url_keeps_full_path() {
+        assert_eq!(
+            transcription_url("http://10.0.0.5:9000/v1/audio/transcriptions/"),
+            "http://10.0.0.5:9000/v1/audio/transcriptions"
+        );
+    }
+}
@@ -1,8 +1,17 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use serde::Deserialize;
+use tokio::sync::oneshot;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 
+use super::vocabulary;
+use super::streaming::{SinkMessage, StreamChannels, StreamConfig, StreamEvent, StreamSession, StreamingProvider};
+use super::ProviderResult;
+use crate::commands::settings::Settings;
+
 // ============================================================================
 // Deepgram Streaming Structures
 // ============================================================================
@@ -26,125 +35,199 @@ struct DeepgramMessage {
     channel: Option<DeepgramChannel>,
 }
 
+/// Deepgram closes the socket after 10 seconds without audio or a KeepAlive
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(4);
+
+/// Nova-3 keyterm prompting accepts up to 500 tokens across all keyterms
+const MAX_KEYTERMS: usize = 50;
+const KEYTERM_CHARS: usize = 1_000;
+
+/// How long to wait for Deepgram to close the socket after `CloseStream`
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Map a server text message to a stream event. Metadata and empty results yield None.
+fn parse_message(text: &str) -> Option<StreamEvent> {
+    let message = serde_json::from_str::<DeepgramMessage>(text).ok()?;
+    match message.msg_type.as_str() {
+        "Results" => {
+            let channel = message.channel?;
+            let transcript = channel.alternatives.first()?.transcript.trim();
+            if transcript.is_empty() {
+                return None;
+            }
+            let is_final = message.is_final.unwrap_or(false) || message.speech_final.unwrap_or(false);
+            Some(if is_final {
+                StreamEvent::Final(transcript.to_string())
+            } else {
+                StreamEvent::Partial(transcript.to_string())
+            })
+        }
+        "SpeechStarted" => Some(StreamEvent::SpeechStarted),
+        "UtteranceEnd" => Some(StreamEvent::UtteranceEnd),
+        _ => None,
+    }
+}
+
 // ============================================================================
 // Deepgram WebSocket Streaming
 // ============================================================================
 
-/// Start Deepgram streaming session
-/// Connects to Deepgram WebSocket and returns a channel for sending audio
-/// Returns: (audio_tx, committed_transcript_rx, partial_transcript_rx)
-pub async fn start_streaming(
-    api_key: String,
-    language: String,
-    smart_format: bool,
-    encoding: Option<String>,
-) -> Result<(
-    tokio::sync::mpsc::Sender<Vec<u8>>,
-    tokio::sync::mpsc::Receiver<String>,
-    tokio::sync::mpsc::Receiver<String>,
-), Box<dyn std::error::Error + Send + Sync>> {
-    
-    // Build WebSocket URL with query parameters (no token in URL)
-    let enc = encoding.unwrap_or_else(|| "opus".to_string());
-    
-    // When smart_format is false, punctuate should also be false (no formatting)
-    // When smart_format is true, punctuate should be true (full formatting)
-    // Add sample_rate for raw audio formats like linear16
-    let url = if enc == "linear16" {
-        format!(
-            "wss://api.deepgram.com/v1/listen?model=nova-3&language={}&punctuate={}&smart_format={}&interim_results=true&endpointing=100&encoding={}&sample_rate=16000",
-            language,
-            smart_format,
-            smart_format,
-            enc
-        )
-    } else {
-        format!(
-            "wss://api.deepgram.com/v1/listen?model=nova-3&language={}&punctuate={}&smart_format={}&interim_results=true&endpointing=100&encoding={}",
+/// Deepgram Nova-3 streaming transcription
+pub struct DeepgramProvider;
+
+#[async_trait]
+impl StreamingProvider for DeepgramProvider {
+    fn id(&self) -> &'static str {
+        "deepgram"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Deepgram"
+    }
+
+    fn api_key<'a>(&self, settings: &'a Settings) -> &'a str {
+        &settings.deepgram_api_key
+    }
+
+    /// The frontend streams 16 kHz PCM16
+    fn encoding(&self) -> Option<&'static str> {
+        Some("linear16")
+    }
+
+    /// Connects to Deepgram WebSocket and starts the send/receive tasks
+    async fn connect(&self, config: StreamConfig) -> ProviderResult<StreamSession> {
+        // Build WebSocket URL with query parameters (no token in URL)
+        let enc = config.encoding.unwrap_or_else(|| "opus".to_string());
+        // Deepgram uses 'multi' for multilingual auto-detect
+        let language = config.language.unwrap_or_else(|| "multi".to_string());
+        let smart_format = config.smart_format;
+        
+        // When smart_format is false, punctuate should also be false (no formatting)
+        // When smart_format is true, punctuate should be true (full formatting)
+        // vad_events and utterance_end_ms enable SpeechStarted and UtteranceEnd messages
+        let mut url = format!(
+            "wss://api.deepgram.com/v1/listen?model=nova-3&language={}&punctuate={}&smart_format={}&interim_results=true&endpointing=100&vad_events=true&utterance_end_ms=1000&encoding={}",
             language,
             smart_format,
             smart_format,
             enc
-        )
-    };
-    
-    // Create WebSocket request with subprotocol authentication (like Electron: ['token', apiKey])
-    let mut request = url.into_client_request()?;
-    
-    // Add Sec-WebSocket-Protocol header with 'token' and the API key
-    request.headers_mut().insert(
-        "Sec-WebSocket-Protocol",
-        format!("token, {}", api_key).parse().unwrap()
-    );
-    
-    // Connect to WebSocket with authentication
-    let (ws_stream, _response) = connect_async(request).await?;
-    
-    let (mut write, mut read) = ws_stream.split();
-    
-    // Create channels for communication
-    let (audio_tx, mut audio_rx) = tokio::sync::mpsc::channel::<Vec<u8>>(100);
-    let (transcript_tx, transcript_rx) = tokio::sync::mpsc::channel::<String>(100);
-    let (partial_tx, partial_rx) = tokio::sync::mpsc::channel::<String>(100);
-    
-    // Spawn task to send audio chunks to Deepgram
-    tokio::spawn(async move {
-        while let Some(audio_data) = audio_rx.recv().await {
-            if audio_data.is_empty() {
-                // Empty data means close connection - wait briefly for final transcripts
-                tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-                let _ = write.send(Message::Close(None)).await;
-                break;
-            }
-            
-            if let Err(_) = write.send(Message::Binary(audio_data)).await {
-                break;
-            }
+        );
+        // Raw audio formats like linear16 need the sample rate
+        if enc == "linear16" {
+            url.push_str("&sample_rate=16000");
         }
-    });
-    
-    // Spawn task to receive transcripts from Deepgram
-    let transcript_tx_clone = transcript_tx.clone();
-    let partial_tx_clone = partial_tx.clone();
-    tokio::spawn(async move {
-        while let Some(msg) = read.next().await {
-            match msg {
-                Ok(Message::Text(text)) => {
-                    // Parse Deepgram message
-                    if let Ok(dg_msg) = serde_json::from_str::<DeepgramMessage>(&text) {
-                        if dg_msg.msg_type == "Results" {
-                            if let Some(channel) = dg_msg.channel {
-                                if let Some(alt) = channel.alternatives.first() {
-                                    let transcript = alt.transcript.trim();
-                                    if !transcript.is_empty() {
-                                        let is_final = dg_msg.is_final.unwrap_or(false)
-                                            || dg_msg.speech_final.unwrap_or(false);
-                                        
-                                        if is_final {
-                                            // Final transcript → committed channel
-                                            if let Err(_) = transcript_tx_clone.send(transcript.to_string()).await {
-                                                break;
-                                            }
-                                        } else {
-                                            // Interim transcript → partial channel (for overlay)
-                                            let _ = partial_tx_clone.send(transcript.to_string()).await;
-                                        }
-                                    }
-                                }
-                            }
+        // Bias recognition toward the user's custom words
+        for term in vocabulary::terms(&config.vocabulary, MAX_KEYTERMS, KEYTERM_CHARS) {
+            url.push_str(&format!("&keyterm={}", urlencoding::encode(&term)));
+        }
+        
+        // Create WebSocket request with subprotocol authentication (like Electron: ['token', apiKey])
+        let mut request = url.into_client_request()?;
+        
+        // Add Sec-WebSocket-Protocol header with 'token' and the API key
+        request.headers_mut().insert(
+            "Sec-WebSocket-Protocol",
+            format!("token, {}", config.api_key).parse()?
+        );
+        
+        // Connect to WebSocket with authentication
+        let (ws_stream, _response) = connect_async(request).await?;
+        
+        let (mut write, mut read) = ws_stream.split();
+        
+        let (session, channels) = StreamSession::channel();
+        let StreamChannels { mut audio_rx, events_tx } = channels;
+        // Resolved when the receive task ends, i.e. Deepgram closed the socket
+        let (closed_tx, mut closed_rx) = oneshot::channel::<()>();
+        
+        // Spawn task to send audio chunks to Deepgram
+        let send_events_tx = events_tx.clone();
+        tokio::spawn(async move {
+            loop {
+                // Long push-to-talk pauses would otherwise time the socket out
+                let message = match tokio::time::timeout(KEEPALIVE_INTERVAL, audio_rx.recv()).await {
+                    Ok(Some(message)) => message,
+                    Ok(None) => SinkMessage::Close,
+                    Err(_) => SinkMessage::KeepAlive,
+                };
+                match message {
+                    SinkMessage::Audio(audio_data) => {
+                        if let Err(e) = write.send(Message::Binary(audio_data)).await {
+                            let _ = send_events_tx.send(StreamEvent::Error(e.into())).await;
+                            break;
                         }
                     }
+                    SinkMessage::KeepAlive => {
+                        let keep_alive = r#"{"type":"KeepAlive"}"#.to_string();
+                        if write.send(Message::Text(keep_alive)).await.is_err() {
+                            break;
+                        }
+                    }
+                    SinkMessage::Close => {
+                        // Finalize flushes the last words as final results; CloseStream
+                        // then lets Deepgram send what is left and close the socket itself
+                        let finalize = r#"{"type":"Finalize"}"#.to_string();
+                        let close_stream = r#"{"type":"CloseStream"}"#.to_string();
+                        if write.send(Message::Text(finalize)).await.is_ok()
+                            && write.send(Message::Text(close_stream)).await.is_ok()
+                        {
+                            let _ = tokio::time::timeout(CLOSE_TIMEOUT, &mut closed_rx).await;
+                        }
+                        // Still open after the timeout (or the messages failed): close it ourselves
+                        let _ = write.send(Message::Close(None)).await;
+                        break;
+                    }
                 }
-                Ok(Message::Close(_)) => {
-                    break;
-                }
-                Err(_) => {
-                    break;
+            }
+        });
+        
+        // Spawn task to receive transcripts from Deepgram
+        tokio::spawn(async move {
+            while let Some(msg) = read.next().await {
+                match msg {
+                    Ok(Message::Text(text)) => {
+                        if let Some(event) = parse_message(&text) {
+                            if events_tx.send(event).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    Ok(Message::Close(_)) => {
+                        break;
+                    }
+                    Err(e) => {
+                        let _ = events_tx.send(StreamEvent::Error(e.into())).await;
+                        break;
+                    }
+                    _ => {}
                 }
-                _ => {}
             }
-        }
-    });
-    
-    Ok((audio_tx, transcript_rx, partial_rx))
+            let _ = closed_tx.send(());
+            let _ = events_tx.send(StreamEvent::Closed).await;
+        });
+        
+        Ok(session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_message() {
+        let result = |transcript: &str, is_final: bool| {
+            format!(
+                r#"{{"type":"Results","is_final":{},"speech_final":false,"channel":{{"alternatives":[{{"transcript":"{}"}}]}}}}"#,
+                is_final, transcript
+            )
+        };
+        assert_eq!(parse_message(&result("hello", false)), Some(StreamEvent::Partial("hello".into())));
+        assert_eq!(parse_message(&result(" hello world ", true)), Some(StreamEvent::Final("hello world".into())));
+        assert_eq!(parse_message(&result("", true)), None);
+        assert_eq!(parse_message(r#"{"type":"SpeechStarted","timestamp":1.2}"#), Some(StreamEvent::SpeechStarted));
+        assert_eq!(parse_message(r#"{"type":"UtteranceEnd","last_word_end":2.5}"#), Some(StreamEvent::UtteranceEnd));
+        assert_eq!(parse_message(r#"{"type":"Metadata","request_id":"abc"}"#), None);
+        assert_eq!(parse_message("not json"), None);
+    }
 }
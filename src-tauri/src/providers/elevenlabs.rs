@@ -1,9 +1,18 @@
+use async_trait::async_trait;
 use base64::Engine;
 use futures_util::{SinkExt, StreamExt};
 use serde::Deserialize;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 
+use super::vocabulary;
+use super::streaming::{SinkMessage, StreamChannels, StreamConfig, StreamEvent, StreamSession, StreamingProvider};
+use super::ProviderResult;
+use crate::commands::settings::Settings;
+
+/// Character budget for the `previous_text` context sent with the first audio chunk
+const CONTEXT_CHARS: usize = 500;
+
 // ============================================================================
 // ElevenLabs Scribe v2 Realtime Streaming Structures
 // ============================================================================
@@ -23,136 +32,154 @@ struct ElevenLabsMessage {
 // ElevenLabs WebSocket Streaming
 // ============================================================================
 
-/// Start ElevenLabs Scribe v2 realtime streaming session
-/// Connects to ElevenLabs WebSocket and returns channels for audio/transcripts
-/// Returns: (audio_tx, committed_transcript_rx, partial_transcript_rx)
-pub async fn start_streaming(
-    api_key: String,
-    language: Option<String>,
-) -> Result<(
-    tokio::sync::mpsc::Sender<Vec<u8>>,
-    tokio::sync::mpsc::Receiver<String>,
-    tokio::sync::mpsc::Receiver<String>,
-), Box<dyn std::error::Error + Send + Sync>> {
-    
-    // Build WebSocket URL with query parameters
-    let mut url = format!(
-        "wss://api.elevenlabs.io/v1/speech-to-text/realtime?model_id=scribe_v2_realtime&audio_format=pcm_16000&commit_strategy=vad&vad_silence_threshold_secs=1.0"
-    );
-    
-    // Add language if specified (omit for auto-detect)
-    if let Some(lang) = language {
-        url.push_str(&format!("&language_code={}", lang));
+/// ElevenLabs Scribe v2 realtime streaming transcription
+pub struct ElevenLabsProvider;
+
+#[async_trait]
+impl StreamingProvider for ElevenLabsProvider {
+    fn id(&self) -> &'static str {
+        "elevenlabs"
     }
-    
-    // Build request with API key in header
-    let mut request = url.into_client_request()?;
-    request.headers_mut().insert(
-        "xi-api-key",
-        api_key.parse().unwrap()
-    );
-    
-    // Connect to WebSocket
-    let (ws_stream, _response) = connect_async(request).await?;
-    let (mut write, mut read) = ws_stream.split();
-    
-    // Wait for session_started event before proceeding
-    let first_msg = read.next().await
-        .ok_or("Connection closed before session_started")?
-        .map_err(|e| format!("WebSocket error waiting for session_started: {}", e))?;
-    
-    if let Message::Text(text) = first_msg {
-        let event: ElevenLabsMessage = serde_json::from_str(&text)
-            .map_err(|e| format!("Failed to parse session event: {}", e))?;
-        if event.message_type != "session_started" {
-            return Err(format!("Expected session_started, got: {}", event.message_type).into());
-        }
-    } else {
-        return Err("Expected text message for session_started".into());
+
+    fn display_name(&self) -> &'static str {
+        "ElevenLabs"
     }
-    
-    // Create channels for communication
-    let (audio_tx, mut audio_rx) = tokio::sync::mpsc::channel::<Vec<u8>>(100);
-    let (transcript_tx, transcript_rx) = tokio::sync::mpsc::channel::<String>(100);
-    let (partial_tx, partial_rx) = tokio::sync::mpsc::channel::<String>(100);
-    
-    // Spawn task to send audio chunks as base64 JSON messages
-    tokio::spawn(async move {
-        while let Some(audio_data) = audio_rx.recv().await {
-            if audio_data.is_empty() {
-                // Empty data means close connection - send a final commit to flush remaining audio
-                let commit_msg = serde_json::json!({
-                    "message_type": "input_audio_chunk",
-                    "audio_base_64": "",
-                    "commit": true,
-                    "sample_rate": 16000
-                });
-                let _ = write.send(Message::Text(commit_msg.to_string())).await;
-                
-                // Wait briefly for final transcripts
-                tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-                let _ = write.send(Message::Close(None)).await;
-                break;
-            }
-            
-            // Encode audio as base64 and send as JSON
-            let base64_audio = base64::engine::general_purpose::STANDARD.encode(&audio_data);
-            let audio_msg = serde_json::json!({
-                "message_type": "input_audio_chunk",
-                "audio_base_64": base64_audio,
-                "commit": false,
-                "sample_rate": 16000
-            });
-            
-            if let Err(_) = write.send(Message::Text(audio_msg.to_string())).await {
-                break;
+
+    fn api_key<'a>(&self, settings: &'a Settings) -> &'a str {
+        &settings.elevenlabs_api_key
+    }
+
+    /// Connects to ElevenLabs WebSocket and waits for session_started
+    async fn connect(&self, config: StreamConfig) -> ProviderResult<StreamSession> {
+        // Build WebSocket URL with query parameters
+        let mut url = "wss://api.elevenlabs.io/v1/speech-to-text/realtime?model_id=scribe_v2_realtime&audio_format=pcm_16000&commit_strategy=vad&vad_silence_threshold_secs=1.0".to_string();
+        
+        // Add language if specified (omit for auto-detect)
+        if let Some(lang) = config.language {
+            url.push_str(&format!("&language_code={}", lang));
+        }
+        
+        // Build request with API key in header
+        let mut request = url.into_client_request()?;
+        request.headers_mut().insert(
+            "xi-api-key",
+            config.api_key.parse()?
+        );
+        
+        // Connect to WebSocket
+        let (ws_stream, _response) = connect_async(request).await?;
+        let (mut write, mut read) = ws_stream.split();
+        
+        // Wait for session_started event before proceeding
+        let first_msg = read.next().await
+            .ok_or("Connection closed before session_started")?
+            .map_err(|e| format!("WebSocket error waiting for session_started: {}", e))?;
+        
+        if let Message::Text(text) = first_msg {
+            let event: ElevenLabsMessage = serde_json::from_str(&text)
+                .map_err(|e| format!("Failed to parse session event: {}", e))?;
+            if event.message_type != "session_started" {
+                return Err(format!("Expected session_started, got: {}", event.message_type).into());
             }
+        } else {
+            return Err("Expected text message for session_started".into());
         }
-    });
-    
-    // Spawn task to receive transcripts (both committed and partial)
-    let transcript_tx_clone = transcript_tx.clone();
-    let partial_tx_clone = partial_tx.clone();
-    tokio::spawn(async move {
-        while let Some(msg) = read.next().await {
-            match msg {
-                Ok(Message::Text(text)) => {
-                    if let Ok(event) = serde_json::from_str::<ElevenLabsMessage>(&text) {
-                        if event.message_type == "committed_transcript" {
-                            // Forward committed (finalized) transcripts
-                            if let Some(transcript_text) = event.text {
-                                let transcript = transcript_text.trim();
-                                if !transcript.is_empty() {
-                                    if let Err(_) = transcript_tx_clone.send(transcript.to_string()).await {
-                                        break;
-                                    }
-                                }
-                            }
-                        } else if event.message_type == "partial_transcript" {
-                            // Forward partial transcripts for overlay display
-                            if let Some(partial_text) = event.text {
-                                let partial = partial_text.trim();
-                                if !partial.is_empty() {
-                                    let _ = partial_tx_clone.send(partial.to_string()).await;
+        
+        let (session, channels) = StreamSession::channel();
+        let StreamChannels { mut audio_rx, events_tx } = channels;
+        
+        // Custom words go out as context text, which is only accepted with the first chunk
+        let mut previous_text = vocabulary::prompt(&config.vocabulary, CONTEXT_CHARS);
+        
+        // Spawn task to send audio chunks as base64 JSON messages
+        let send_events_tx = events_tx.clone();
+        tokio::spawn(async move {
+            while let Some(message) = audio_rx.recv().await {
+                match message {
+                    SinkMessage::Audio(audio_data) => {
+                        // Encode audio as base64 and send as JSON
+                        let base64_audio = base64::engine::general_purpose::STANDARD.encode(&audio_data);
+                        let mut audio_msg = serde_json::json!({
+                            "message_type": "input_audio_chunk",
+                            "audio_base_64": base64_audio,
+                            "commit": false,
+                            "sample_rate": 16000
+                        });
+                        if let Some(context) = previous_text.take() {
+                            audio_msg["previous_text"] = context.into();
+                        }
+                        
+                        if let Err(e) = write.send(Message::Text(audio_msg.to_string())).await {
+                            let _ = send_events_tx.send(StreamEvent::Error(e.into())).await;
+                            break;
+                        }
+                    }
+                    SinkMessage::KeepAlive => {
+                        if write.send(Message::Ping(Vec::new())).await.is_err() {
+                            break;
+                        }
+                    }
+                    SinkMessage::Close => {
+                        // Send a final commit to flush remaining audio
+                        let commit_msg = serde_json::json!({
+                            "message_type": "input_audio_chunk",
+                            "audio_base_64": "",
+                            "commit": true,
+                            "sample_rate": 16000
+                        });
+                        let _ = write.send(Message::Text(commit_msg.to_string())).await;
+                        
+                        // Wait briefly for final transcripts
+                        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+                        let _ = write.send(Message::Close(None)).await;
+                        break;
+                    }
+                }
+            }
+        });
+        
+        // Spawn task to receive transcripts (both committed and partial)
+        tokio::spawn(async move {
+            while let Some(msg) = read.next().await {
+                match msg {
+                    Ok(Message::Text(text)) => {
+                        if let Ok(event) = serde_json::from_str::<ElevenLabsMessage>(&text) {
+                            let stream_event = match event.message_type.as_str() {
+                                // Committed (finalized) transcripts
+                                "committed_transcript" => event.text
+                                    .map(|t| t.trim().to_string())
+                                    .filter(|t| !t.is_empty())
+                                    .map(StreamEvent::Final),
+                                // Partial transcripts for overlay display
+                                "partial_transcript" => event.text
+                                    .map(|t| t.trim().to_string())
+                                    .filter(|t| !t.is_empty())
+                                    .map(StreamEvent::Partial),
+                                "error" => Some(StreamEvent::Error(
+                                    event.error.unwrap_or_else(|| "Unknown error".to_string()).into()
+                                )),
+                                _ => None,
+                            };
+                            if let Some(stream_event) = stream_event {
+                                if events_tx.send(stream_event).await.is_err() {
+                                    return;
                                 }
                             }
-                        } else if event.message_type == "error" {
-                            if let Some(error) = event.error {
-                                eprintln!("[ElevenLabs] Server error: {}", error);
-                            }
                         }
                     }
+                    Ok(Message::Close(_)) => {
+                        break;
+                    }
+                    Err(e) => {
+                        let _ = events_tx.send(StreamEvent::Error(e.into())).await;
+                        break;
+                    }
+                    _ => {}
                 }
-                Ok(Message::Close(_)) => {
-                    break;
-                }
-                Err(_) => {
-                    break;
-                }
-                _ => {}
             }
-        }
-    });
-    
-    Ok((audio_tx, transcript_rx, partial_rx))
+            let _ = events_tx.send(StreamEvent::Closed).await;
+        });
+        
+        Ok(session)
+    }
 }
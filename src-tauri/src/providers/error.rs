@@ -0,0 +1,245 @@
+use std::fmt;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+/// Failure of a provider request.
+/// Serialized to the frontend as `{ code, message, retry_after_secs?, status? }`;
+/// `code` is stable and used to pick a localized message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProviderError {
+    /// API key missing or rejected (401/403)
+    Auth(String),
+    /// Too many requests (429); `retry_after` comes from the Retry-After header
+    RateLimited { retry_after: Option<Duration> },
+    /// Connection, DNS or TLS failure, or a dropped socket
+    Network(String),
+    /// No response within the request timeout or retry deadline
+    Timeout,
+    /// Audio the provider cannot process (too small, too large, wrong format)
+    BadAudio(String),
+    /// Account out of credits or over its usage quota
+    QuotaExceeded(String),
+    /// Any other non-2xx response
+    Server { status: u16, body: String },
+    /// Missing configuration, unexpected response or internal failure
+    Other(String),
+}
+
+impl ProviderError {
+    /// Stable identifier sent to the frontend
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Auth(_) => "auth",
+            Self::RateLimited { .. } => "rate_limited",
+            Self::Network(_) => "network",
+            Self::Timeout => "timeout",
+            Self::BadAudio(_) => "bad_audio",
+            Self::QuotaExceeded(_) => "quota_exceeded",
+            Self::Server { .. } => "server",
+            Self::Other(_) => "other",
+        }
+    }
+
+    /// Transient failures worth retrying on the same provider
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RateLimited { .. } | Self::Network(_) | Self::Timeout => true,
+            Self::Server { status, .. } => *status >= 500 && *status != 501,
+            _ => false,
+        }
+    }
+
+    /// Delay requested by the server before retrying
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Classify a non-2xx HTTP response
+    pub fn from_status(status: u16, retry_after: Option<Duration>, body: String) -> Self {
+        let lower = body.to_lowercase();
+        let mentions_quota = lower.contains("quota") || lower.contains("insufficient") || lower.contains("credit");
+        match status {
+            401 | 403 => Self::Auth(body),
+            402 => Self::QuotaExceeded(body),
+            429 if mentions_quota => Self::QuotaExceeded(body),
+            429 => Self::RateLimited { retry_after },
+            408 => Self::Timeout,
+            413 | 415 => Self::BadAudio(body),
+            400 | 422 if lower.contains("audio") || lower.contains("file") => Self::BadAudio(body),
+            _ => Self::Server { status, body },
+        }
+    }
+}
+
+/// Parse a Retry-After header value: a delay in seconds or an HTTP date
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    // A date in the past means "retry now"
+    Some((date.with_timezone(&Utc) - now).to_std().unwrap_or_default())
+}
+
+impl fmt::Display for ProviderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auth(detail) if detail.is_empty() => write!(f, "Invalid API key."),
+            Self::Auth(detail) => write!(f, "Authentication failed: {}", detail),
+            Self::RateLimited { .. } => write!(f, "Rate limit exceeded. Please wait a moment."),
+            Self::Network(detail) => write!(f, "Network error. Check your connection. ({})", detail),
+            Self::Timeout => write!(f, "Request timeout"),
+            Self::BadAudio(detail) => write!(f, "Audio rejected: {}", detail),
+            Self::QuotaExceeded(detail) => write!(f, "Quota exceeded: {}", detail),
+            Self::Server { status, body } => write!(f, "API error ({}): {}", status, body),
+            Self::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for ProviderError {}
+
+impl Serialize for ProviderError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ProviderError", 4)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        match self {
+            Self::RateLimited { retry_after } => {
+                state.serialize_field("retry_after_secs", &retry_after.map(|d| d.as_secs()))?;
+            }
+            Self::Server { status, .. } => state.serialize_field("status", status)?,
+            _ => state.skip_field("status")?,
+        }
+        state.end()
+    }
+}
+
+impl From<&str> for ProviderError {
+    fn from(message: &str) -> Self {
+        Self::Other(message.to_string())
+    }
+}
+
+impl From<String> for ProviderError {
+    fn from(message: String) -> Self {
+        Self::Other(message)
+    }
+}
+
+impl From<reqwest::Error> for ProviderError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            Self::Timeout
+        } else if e.is_connect() || e.is_request() {
+            Self::Network(e.to_string())
+        } else if let Some(status) = e.status() {
+            Self::from_status(status.as_u16(), None, e.to_string())
+        } else if e.is_decode() {
+            Self::Other(format!("Invalid response: {}", e))
+        } else {
+            Self::Other(e.to_string())
+        }
+    }
+}
+
+impl From<serde_json::Error> for ProviderError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Other(format!("Invalid response: {}", e))
+    }
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for ProviderError {
+    fn from(e: tokio_tungstenite::tungstenite::Error) -> Self {
+        use tokio_tungstenite::tungstenite::Error as WsError;
+        match e {
+            // Handshake rejected with an HTTP status (bad key, rate limit, ...)
+            WsError::Http(response) => {
+                let body = response
+                    .body()
+                    .as_ref()
+                    .map(|b| String::from_utf8_lossy(b).into_owned())
+                    .unwrap_or_default();
+                Self::from_status(response.status().as_u16(), None, body)
+            }
+            other => Self::Network(other.to_string()),
+        }
+    }
+}
+
+/// Only API keys end up in hand-built headers
+impl From<tokio_tungstenite::tungstenite::http::header::InvalidHeaderValue> for ProviderError {
+    fn from(_: tokio_tungstenite::tungstenite::http::header::InvalidHeaderValue) -> Self {
+        Self::Auth("API key contains invalid characters".to_string())
+    }
+}
+
+impl From<tokio::task::JoinError> for ProviderError {
+    fn from(e: tokio::task::JoinError) -> Self {
+        Self::Other(format!("Task join error: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_classification() {
+        assert_eq!(ProviderError::from_status(401, None, String::new()).code(), "auth");
+        assert_eq!(
+            ProviderError::from_status(429, Some(Duration::from_secs(2)), "slow down".into()),
+            ProviderError::RateLimited { retry_after: Some(Duration::from_secs(2)) }
+        );
+        assert_eq!(
+            ProviderError::from_status(429, None, "insufficient_quota".into()).code(),
+            "quota_exceeded"
+        );
+        assert_eq!(ProviderError::from_status(413, None, String::new()).code(), "bad_audio");
+        assert_eq!(ProviderError::from_status(400, None, "invalid audio file".into()).code(), "bad_audio");
+        assert_eq!(ProviderError::from_status(400, None, "bad model".into()).code(), "server");
+    }
+
+    #[test]
+    fn test_retryable_errors() {
+        assert!(ProviderError::from_status(429, None, String::new()).is_retryable());
+        assert!(ProviderError::from_status(503, None, String::new()).is_retryable());
+        assert!(ProviderError::Timeout.is_retryable());
+        assert!(!ProviderError::from_status(401, None, String::new()).is_retryable());
+        assert!(!ProviderError::from_status(501, None, String::new()).is_retryable());
+        assert!(!ProviderError::BadAudio("too small".into()).is_retryable());
+    }
+
+    #[test]
+    fn test_serialized_with_code() {
+        let json = serde_json::to_value(ProviderError::RateLimited {
+            retry_after: Some(Duration::from_secs(3)),
+        })
+        .unwrap();
+        assert_eq!(json["code"], "rate_limited");
+        assert_eq!(json["retry_after_secs"], 3);
+        assert!(json["message"].as_str().unwrap().contains("Rate limit"));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = DateTime::parse_from_rfc2822("Wed, 21 Oct 2026 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(parse_retry_after("3", now), Some(Duration::from_secs(3)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2026 07:28:05 GMT", now),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2026 07:27:00 GMT", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+}
@@ -0,0 +1,95 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use super::ProviderError;
+
+/// Event emitted for every provider attempt in a fallback chain
+pub const ATTEMPT_EVENT: &str = "provider-attempt";
+
+/// Request type a provider attempt belongs to
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttemptKind {
+    Transcription,
+    Rewrite,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttemptStatus {
+    Started,
+    Succeeded,
+    Failed,
+    /// Not tried (missing API key, audio too large, ...)
+    Skipped,
+    /// Cancelled because a hedged request to another provider answered first
+    Superseded,
+}
+
+/// Payload of the `provider-attempt` event
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderAttempt {
+    pub kind: AttemptKind,
+    pub provider: String,
+    /// 1-based position in the chain
+    pub attempt: usize,
+    /// Number of providers in the chain
+    pub total: usize,
+    pub status: AttemptStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ProviderError>,
+}
+
+impl ProviderAttempt {
+    pub fn new(kind: AttemptKind, provider: &str, attempt: usize, total: usize) -> Self {
+        Self {
+            kind,
+            provider: provider.to_string(),
+            attempt,
+            total,
+            status: AttemptStatus::Started,
+            error: None,
+        }
+    }
+
+    /// Emit this attempt with the given status
+    pub fn report(&self, app: &AppHandle, status: AttemptStatus, error: Option<&ProviderError>) {
+        let payload = Self {
+            status,
+            error: error.cloned(),
+            ..self.clone()
+        };
+        let _ = app.emit(ATTEMPT_EVENT, payload);
+    }
+}
+
+/// Ordered provider ids to try: the selected provider first, then the configured
+/// fallbacks, skipping blanks and duplicates
+pub fn fallback_chain(primary: &str, fallbacks: &[String]) -> Vec<String> {
+    let mut chain: Vec<String> = Vec::with_capacity(fallbacks.len() + 1);
+    for id in std::iter::once(primary).chain(fallbacks.iter().map(String::as_str)) {
+        let id = id.trim();
+        if !id.is_empty() && !chain.iter().any(|existing| existing == id) {
+            chain.push(id.to_string());
+        }
+    }
+    chain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_starts_with_primary() {
+        let fallbacks = vec!["fireworks".to_string(), "mistral".to_string()];
+        assert_eq!(fallback_chain("groq", &fallbacks), vec!["groq", "fireworks", "mistral"]);
+        assert_eq!(fallback_chain("groq", &[]), vec!["groq"]);
+    }
+
+    #[test]
+    fn test_chain_skips_duplicates_and_blanks() {
+        let fallbacks = vec![" ".to_string(), "groq".to_string(), "mistral".to_string(), "mistral ".to_string()];
+        assert_eq!(fallback_chain("groq", &fallbacks), vec!["groq", "mistral"]);
+    }
+}
@@ -1,22 +1,11 @@
-use reqwest;
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::sync::OnceLock;
-
-static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
-
-fn get_http_client() -> &'static reqwest::Client {
-    HTTP_CLIENT.get_or_init(|| {
-        reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .pool_max_idle_per_host(10)
-            .pool_idle_timeout(std::time::Duration::from_secs(90))
-            .tcp_keepalive(std::time::Duration::from_secs(60))
-            .http2_keep_alive_interval(std::time::Duration::from_secs(30))
-            .http2_keep_alive_timeout(std::time::Duration::from_secs(20))
-            .build()
-            .expect("Failed to create HTTP client")
-    })
-}
+
+use super::{http, retry, vocabulary, ProviderCapabilities, ProviderError, ProviderResult, RewriteModel, RewriteProvider, RewriteRequest, Transcript, TranscriptionProvider, TranscriptionRequest};
+use crate::commands::settings::Settings;
+
+/// Custom words listed in the transcription instruction
+const MAX_VOCABULARY_TERMS: usize = 100;
 
 // ============================================================================
 // Whisper Transcription Structures
@@ -47,14 +36,20 @@ struct ThinkingConfig {
     thinking_level: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Serialize)]
 struct GenerationConfig {
     #[serde(rename = "thinkingConfig")]
-    thinking_config: ThinkingConfig,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking_config: Option<ThinkingConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(rename = "maxOutputTokens")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_output_tokens: Option<u32>,
 }
 
 #[derive(Debug, Serialize)]
-struct TranscriptionRequest {
+struct GenerateContentRequest {
     contents: Vec<Content>,
     #[serde(rename = "generationConfig")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -85,94 +80,115 @@ struct TranscriptionResponse {
 // Whisper Transcription API
 // ============================================================================
 
-/// Transcribe audio using Gemini Flash Lite with batch processing
-/// Supports optional language parameter for better accuracy
-pub async fn transcribe_verbose(
-    audio_data: Vec<u8>,
-    api_key: String,
-    _language: Option<String>,
-) -> Result<String, Box<dyn std::error::Error>> {
-    if audio_data.len() < 100 {
-        return Err("Audio data too small".into());
+/// Gemini 3.1 Flash Lite batch transcription
+pub struct GeminiProvider;
+
+#[async_trait]
+impl TranscriptionProvider for GeminiProvider {
+    fn id(&self) -> &'static str {
+        "gemini"
     }
-    
-    let client = get_http_client();
-    
-    // Encode audio as base64
-    let base64_audio = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &audio_data);
-    
-    // Construct request body
-    let request_body = TranscriptionRequest {
-        contents: vec![Content {
-            role: "user".to_string(),
-            parts: vec![
-                ContentPart::Text {
-                    text: "Generate a transcript of the speech.".to_string(),
-                },
-                ContentPart::InlineData {
-                    inline_data: InlineData {
-                        mime_type: "audio/wav".to_string(),
-                        data: base64_audio,
-                    },
-                },
-            ],
-        }],
-        generation_config: Some(GenerationConfig {
-            thinking_config: ThinkingConfig {
-                thinking_level: "MINIMAL".to_string(),
-            },
-        }),
-    };
-    
-    // API key goes in URL query param
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/gemini-3.1-flash-lite-preview:generateContent?key={}",
-        urlencoding::encode(&api_key)
-    );
-    
-    let response = client
-        .post(&url)
-        .header("Content-Type", "application/json")
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| {
-            if e.is_timeout() {
-                "Request timeout".to_string()
-            } else if e.is_connect() {
-                "Connection failed - check internet".to_string()
-            } else {
-                e.to_string()
-            }
-        })?;
-    
-    // Check status code
-    let status = response.status();
-    if !status.is_success() {
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("API error ({}): {}", status.as_u16(), error_text).into());
+
+    fn display_name(&self) -> &'static str {
+        "Gemini 3.1 Flash Lite"
     }
-    
-    let result: TranscriptionResponse = response.json().await?;
-    
-    // Extract text from candidates
-    if let Some(candidates) = result.candidates {
-        for candidate in candidates {
-            if let Some(content) = candidate.content {
-                if let Some(parts) = content.parts {
-                    for part in parts {
-                        if let Some(text) = part.text {
-                            if !text.trim().is_empty() {
-                                return Ok(text);
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            languages: &[],
+            max_upload_bytes: 20 * 1024 * 1024,
+            timestamps: false,
+        }
+    }
+
+    fn api_key<'a>(&self, settings: &'a Settings) -> &'a str {
+        &settings.gemini_api_key
+    }
+
+    /// Transcribe audio using Gemini Flash Lite with batch processing
+    /// The language is always auto-detected
+    async fn transcribe(&self, request: TranscriptionRequest) -> ProviderResult<Transcript> {
+        if request.audio.len() < 100 {
+            return Err(ProviderError::BadAudio("Audio data too small".to_string()));
+        }
+
+        // Encode audio as base64
+        let base64_audio = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &request.audio);
+
+        // Spell the user's custom words the way they wrote them
+        let mut instruction = "Generate a transcript of the speech.".to_string();
+        let terms = vocabulary::terms(&request.vocabulary, MAX_VOCABULARY_TERMS, usize::MAX);
+        if !terms.is_empty() {
+            instruction.push_str(&format!(
+                " The speaker may use these terms; spell them exactly as written: {}.",
+                terms.join(", ")
+            ));
+        }
+        // Keep style and names consistent with the segment before
+        if let Some(context) = request.context.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+            instruction.push_str(&format!(
+                " It continues this earlier text, which must not be repeated: \"{}\"",
+                context
+            ));
+        }
+
+        // Construct request body
+        let request_body = GenerateContentRequest {
+            contents: vec![Content {
+                role: "user".to_string(),
+                parts: vec![
+                    ContentPart::Text { text: instruction },
+                    ContentPart::InlineData {
+                        inline_data: InlineData {
+                            mime_type: "audio/wav".to_string(),
+                            data: base64_audio,
+                        },
+                    },
+                ],
+            }],
+            generation_config: Some(GenerationConfig {
+                thinking_config: Some(ThinkingConfig {
+                    thinking_level: "MINIMAL".to_string(),
+                }),
+                ..Default::default()
+            }),
+        };
+
+        // API key goes in URL query param
+        let url = format!(
+            "https://generativelanguage.googleapis.com/v1beta/models/gemini-3.1-flash-lite-preview:generateContent?key={}",
+            urlencoding::encode(&request.api_key)
+        );
+
+        let response = retry::send(&request.retry, || {
+            Ok(http::client()
+                .post(&url)
+                .header("Content-Type", "application/json")
+                .json(&request_body))
+        })
+        .await?;
+
+        let result: TranscriptionResponse = response.json().await?;
+
+        // Extract text from candidates
+        if let Some(candidates) = result.candidates {
+            for candidate in candidates {
+                if let Some(content) = candidate.content {
+                    if let Some(parts) = content.parts {
+                        for part in parts {
+                            if let Some(text) = part.text {
+                                if !text.trim().is_empty() {
+                                    return Ok(Transcript::from_text(text));
+                                }
                             }
                         }
                     }
                 }
             }
         }
+
+        Err("No text in response".into())
     }
-    
-    Err("No text in response".into())
 }
 
 // ============================================================================
@@ -202,95 +218,108 @@ struct ChatRequest {
 // Chat Completion API
 // ============================================================================
 
-/// Rewrite text using a specified Gemini model
-/// Used for text rewriting and transformation
-pub async fn rewrite_text(
-    text: String,
-    prompt: String,
-    api_key: String,
-    model: String,
-) -> Result<String, Box<dyn std::error::Error>> {
-    // Validate inputs
-    if prompt.trim().is_empty() {
-        return Err("Prompt is required".into());
+/// Rewrite text using a Gemini model. Each selectable model is its own provider id.
+pub struct GeminiRewriteProvider {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub model: &'static str,
+}
+
+/// Gemini 3.1 Flash Lite (rewrite)
+pub const GEMINI_FLASH_LITE: GeminiRewriteProvider = GeminiRewriteProvider {
+    id: "gemini-flash-lite",
+    name: "Gemini 3.1 Flash Lite",
+    model: "gemini-3.1-flash-lite-preview",
+};
+
+/// Gemini 3 Flash (rewrite)
+pub const GEMINI_FLASH: GeminiRewriteProvider = GeminiRewriteProvider {
+    id: "gemini-flash",
+    name: "Gemini 3 Flash",
+    model: "gemini-3-flash-preview",
+};
+
+#[async_trait]
+impl RewriteProvider for GeminiRewriteProvider {
+    fn id(&self) -> &'static str {
+        self.id
     }
-    
-    if text.trim().is_empty() {
-        return Err("Text is required".into());
+
+    fn display_name(&self) -> &'static str {
+        self.name
     }
-    
-    let client = get_http_client();
-    
-    // Construct the request body
-    let mut request_body = ChatRequest {
-        contents: vec![ChatContent {
-            role: "user".to_string(),
-            parts: vec![
-                ChatPart { text: prompt },
-                ChatPart { text },
-            ],
-        }],
-        generation_config: None,
-    };
-
-    // If using the 3.1 flash lite model, apply the MINIMAL thinking level
-    if model == "gemini-3.1-flash-lite-preview" {
-        request_body.generation_config = Some(GenerationConfig {
-            thinking_config: ThinkingConfig {
-                thinking_level: "MINIMAL".to_string(),
-            },
-        });
+
+    fn default_model(&self) -> RewriteModel {
+        RewriteModel::new(self.model, None, None)
     }
-    
-    // API key goes in URL query param
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-        model,
-        urlencoding::encode(&api_key)
-    );
-    
-    let response = client
-        .post(&url)
-        .header("Content-Type", "application/json")
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| {
-            if e.is_timeout() {
-                "Request timeout".to_string()
-            } else if e.is_connect() {
-                "Connection failed - check internet".to_string()
-            } else {
-                e.to_string()
-            }
-        })?;
-    
-    // Check status code
-    let status = response.status();
-    if !status.is_success() {
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("API error ({}): {}", status.as_u16(), error_text).into());
+
+    fn api_key<'a>(&self, settings: &'a Settings) -> &'a str {
+        &settings.gemini_api_key
     }
-    
-    let result: TranscriptionResponse = response.json().await?;
-    
-    // Extract text from candidates
-    if let Some(candidates) = result.candidates {
-        for candidate in candidates {
-            if let Some(content) = candidate.content {
-                if let Some(parts) = content.parts {
-                    for part in parts {
-                        if let Some(text) = part.text {
-                            let trimmed = text.trim();
-                            if !trimmed.is_empty() {
-                                return Ok(trimmed.to_string());
+
+    async fn rewrite(&self, request: RewriteRequest) -> ProviderResult<String> {
+        let model = request.model;
+        
+        // Flash Lite models get the MINIMAL thinking level
+        let thinking_config = model.model.contains("flash-lite").then(|| ThinkingConfig {
+            thinking_level: "MINIMAL".to_string(),
+        });
+        let generation_config = GenerationConfig {
+            thinking_config,
+            temperature: model.temperature,
+            max_output_tokens: model.max_tokens,
+        };
+        let has_generation_config = generation_config.thinking_config.is_some()
+            || generation_config.temperature.is_some()
+            || generation_config.max_output_tokens.is_some();
+        
+        // Construct the request body
+        let request_body = ChatRequest {
+            contents: vec![ChatContent {
+                role: "user".to_string(),
+                parts: vec![
+                    ChatPart { text: request.prompt },
+                    ChatPart { text: request.text },
+                ],
+            }],
+            generation_config: has_generation_config.then_some(generation_config),
+        };
+        
+        // API key goes in URL query param
+        let url = format!(
+            "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+            model.model,
+            urlencoding::encode(&request.api_key)
+        );
+        
+        let response = retry::send(&request.retry, || {
+            Ok(http::client()
+                .post(&url)
+                .header("Content-Type", "application/json")
+                .json(&request_body))
+        })
+        .await?;
+        
+        let result: TranscriptionResponse = response.json().await?;
+        
+        // Extract text from candidates
+        if let Some(candidates) = result.candidates {
+            for candidate in candidates {
+                if let Some(content) = candidate.content {
+                    if let Some(parts) = content.parts {
+                        for part in parts {
+                            if let Some(text) = part.text {
+                                let trimmed = text.trim();
+                                if !trimmed.is_empty() {
+                                    return Ok(trimmed.to_string());
+                                }
                             }
                         }
                     }
                 }
             }
         }
+        
+        Err("No content in response".into())
     }
-    
-    Err("No content in response".into())
 }
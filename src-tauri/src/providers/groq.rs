@@ -1,126 +1,84 @@
+use async_trait::async_trait;
 use reqwest::multipart;
-use serde::{Deserialize, Serialize};
-use std::sync::OnceLock;
-
-// Reusable HTTP client with connection pooling
-static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
-
-fn get_http_client() -> &'static reqwest::Client {
-    HTTP_CLIENT.get_or_init(|| {
-        reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .pool_max_idle_per_host(10)
-            .pool_idle_timeout(std::time::Duration::from_secs(90))
-            .tcp_keepalive(std::time::Duration::from_secs(60))
-            .http2_keep_alive_interval(std::time::Duration::from_secs(30))
-            .http2_keep_alive_timeout(std::time::Duration::from_secs(20))
-            .build()
-            .expect("Failed to create HTTP client")
-    })
-}
+use super::chat::{self, ChatCompletionRequest, ChatMessage};
+use super::{http, retry, vocabulary, ProviderCapabilities, ProviderError, ProviderResult, RewriteModel, RewriteProvider, RewriteRequest, Transcript, TranscriptionProvider, TranscriptionRequest, WHISPER_LANGUAGES};
+use super::transcript::VerboseJson;
+use crate::commands::settings::Settings;
 
 // ============================================================================
-// Whisper Transcription Structures
+// Whisper Transcription API
 // ============================================================================
 
-#[derive(Debug, Deserialize)]
-struct WhisperResponse {
-    text: String,
-}
+/// Groq Whisper-Large-v3-Turbo batch transcription
+pub struct GroqProvider;
 
-// ============================================================================
-// Whisper Transcription API
-// ============================================================================
+#[async_trait]
+impl TranscriptionProvider for GroqProvider {
+    fn id(&self) -> &'static str {
+        "groq"
+    }
 
-/// Transcribe audio using Groq Whisper API with batch processing
-/// Supports optional language parameter for better accuracy
-pub async fn transcribe_verbose(
-    audio_data: Vec<u8>,
-    api_key: String,
-    language: Option<String>,
-) -> Result<String, Box<dyn std::error::Error>> {
-    // Validate audio data
-    if audio_data.len() < 100 {
-        return Err("Audio data too small".into());
+    fn display_name(&self) -> &'static str {
+        "Groq Whisper"
     }
-    
-    let client = get_http_client();
-    
-    // Create multipart form
-    let part = multipart::Part::bytes(audio_data)
-        .file_name("output.wav")
-        .mime_str("audio/wav")?;
-    
-    let mut form = multipart::Form::new()
-        .part("file", part)
-        .text("model", "whisper-large-v3-turbo")
-        .text("response_format", "verbose_json");
-    
-    // Add language if specified
-    if let Some(lang) = language {
-        form = form.text("language", lang);
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            languages: WHISPER_LANGUAGES,
+            max_upload_bytes: 25 * 1024 * 1024,
+            timestamps: true,
+        }
     }
-    
-    let response = client
-        .post("https://api.groq.com/openai/v1/audio/transcriptions")
-        .header("Authorization", format!("Bearer {}", api_key))
-        .multipart(form)
-        .send()
-        .await
-        .map_err(|e| {
-            if e.is_timeout() {
-                "Request timeout".into()
-            } else if e.is_connect() {
-                "Connection failed - check internet".into()
-            } else {
-                Box::new(e) as Box<dyn std::error::Error>
-            }
-        })?;
-    
-    // Check status code
-    let status = response.status();
-    if !status.is_success() {
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("API error ({}): {}", status.as_u16(), error_text).into());
+
+    fn api_key<'a>(&self, settings: &'a Settings) -> &'a str {
+        &settings.groq_api_key
     }
-    
-    let result: WhisperResponse = response.json().await?;
-    Ok(result.text)
-}
 
-// ============================================================================
-// Chat Completion Structures
-// ============================================================================
+    /// Transcribe audio using Groq Whisper API with batch processing
+    /// Supports optional language parameter for better accuracy
+    async fn transcribe(&self, request: TranscriptionRequest) -> ProviderResult<Transcript> {
+        // Validate audio data
+        if request.audio.len() < 100 {
+            return Err(ProviderError::BadAudio("Audio data too small".to_string()));
+        }
 
-#[derive(Debug, Serialize)]
-struct ChatMessage {
-    role: String,
-    content: String,
-}
+        // Create multipart form (rebuilt for every retry attempt)
+        let build_form = || -> ProviderResult<multipart::Form> {
+            let part = multipart::Part::bytes(request.audio.clone())
+                .file_name("output.wav")
+                .mime_str("audio/wav")?;
 
-#[derive(Debug, Serialize)]
-struct ChatCompletionRequest {
-    model: String,
-    messages: Vec<ChatMessage>,
-    temperature: f32,
-    top_p: f32,
-    stream: bool,
-    max_completion_tokens: u32,
-}
+            let mut form = multipart::Form::new()
+                .part("file", part)
+                .text("model", "whisper-large-v3-turbo")
+                .text("response_format", "verbose_json")
+                .text("timestamp_granularities[]", "segment")
+                .text("timestamp_granularities[]", "word");
 
-#[derive(Debug, Deserialize)]
-struct ChatChoice {
-    message: ChatMessageResponse,
-}
+            // Add language if specified
+            if let Some(lang) = &request.language {
+                form = form.text("language", lang.clone());
+            }
 
-#[derive(Debug, Deserialize)]
-struct ChatMessageResponse {
-    content: String,
-}
+            // Bias recognition toward the user's custom words and the previous segment
+            if let Some(prompt) = vocabulary::whisper_prompt(&request.vocabulary, request.context.as_deref()) {
+                form = form.text("prompt", prompt);
+            }
+
+            Ok(form)
+        };
 
-#[derive(Debug, Deserialize)]
-struct ChatCompletionResponse {
-    choices: Vec<ChatChoice>,
+        let response = retry::send(&request.retry, || {
+            Ok(http::client()
+                .post("https://api.groq.com/openai/v1/audio/transcriptions")
+                .header("Authorization", format!("Bearer {}", request.api_key))
+                .multipart(build_form()?))
+        })
+        .await?;
+
+        let result: VerboseJson = response.json().await?;
+        Ok(result.into())
+    }
 }
 
 // ============================================================================
@@ -128,69 +86,44 @@ struct ChatCompletionResponse {
 // ============================================================================
 
 /// Rewrite text using Groq chat completions API
-/// Used for text rewriting and transformation
-pub async fn rewrite_text(
-    text: String,
-    prompt: String,
-    api_key: String,
-) -> Result<String, Box<dyn std::error::Error>> {
-    // Validate inputs
-    if prompt.trim().is_empty() {
-        return Err("Prompt is required".into());
+pub struct GroqRewriteProvider;
+
+#[async_trait]
+impl RewriteProvider for GroqRewriteProvider {
+    fn id(&self) -> &'static str {
+        "groq"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Groq GPT-OSS-120B"
     }
-    
-    if text.trim().is_empty() {
-        return Err("Text is required".into());
+
+    fn default_model(&self) -> RewriteModel {
+        RewriteModel::new("openai/gpt-oss-120b", Some(0.2), Some(1024))
     }
-    
-    let client = get_http_client();
-    
-    // Construct the request body
-    let request_body = ChatCompletionRequest {
-        model: "openai/gpt-oss-120b".to_string(),
-        messages: vec![ChatMessage {
-            role: "user".to_string(),
-            content: format!("{}\n\n{}", prompt, text),
-        }],
-        temperature: 0.2,
-        top_p: 1.0,
-        stream: false,
-        max_completion_tokens: 1024,
-    };
-    
-    let response = client
-        .post("https://api.groq.com/openai/v1/chat/completions")
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("Content-Type", "application/json")
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| {
-            if e.is_timeout() {
-                "Request timeout".into()
-            } else if e.is_connect() {
-                "Connection failed - check internet".into()
-            } else {
-                Box::new(e) as Box<dyn std::error::Error>
-            }
-        })?;
-    
-    // Check status code
-    let status = response.status();
-    if !status.is_success() {
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("API error ({}): {}", status.as_u16(), error_text).into());
+
+    fn api_key<'a>(&self, settings: &'a Settings) -> &'a str {
+        &settings.groq_api_key
     }
-    
-    let result: ChatCompletionResponse = response.json().await?;
-    
-    // Extract content from first choice
-    if let Some(choice) = result.choices.first() {
-        let content = choice.message.content.trim();
-        if !content.is_empty() {
-            return Ok(content.to_string());
+
+    async fn rewrite(&self, request: RewriteRequest) -> ProviderResult<String> {
+        let mut request_body = ChatCompletionRequest::new(
+            &request.model,
+            vec![ChatMessage::user(format!("{}\n\n{}", request.prompt, request.text))],
+        )
+        .with("top_p", 1.0);
+
+        // Groq names the token limit max_completion_tokens
+        if let Some(max_tokens) = request_body.max_tokens.take() {
+            request_body = request_body.with("max_completion_tokens", max_tokens);
         }
+
+        chat::complete(
+            "https://api.groq.com/openai/v1/chat/completions",
+            &request.api_key,
+            &request_body,
+            &request.retry,
+        )
+        .await
     }
-    
-    Err("No content in response".into())
 }
@@ -0,0 +1,164 @@
+use std::future::Future;
+use std::time::Duration;
+
+use super::{ProviderResult, Transcript, TranscriptionProvider, TranscriptionRequest};
+
+/// Which request of a hedged pair produced the result
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Leg {
+    Primary,
+    Secondary,
+}
+
+/// Run `primary`, and if it has not finished after `delay`, `secondary` alongside it.
+/// The first success wins; the other future is dropped, which cancels its request.
+/// If the primary fails before `delay` its error is returned without hedging (fallbacks
+/// handle failures). When both fail, the primary's error is returned.
+pub async fn race<T, E>(
+    primary: impl Future<Output = Result<T, E>>,
+    secondary: impl Future<Output = Result<T, E>>,
+    delay: Duration,
+) -> Result<(T, Leg), E> {
+    tokio::pin!(primary);
+    tokio::select! {
+        result = &mut primary => return result.map(|value| (value, Leg::Primary)),
+        _ = tokio::time::sleep(delay) => {}
+    }
+
+    tokio::pin!(secondary);
+    tokio::select! {
+        result = &mut primary => match result {
+            Ok(value) => Ok((value, Leg::Primary)),
+            Err(e) => secondary.await.map(|value| (value, Leg::Secondary)).map_err(|_| e),
+        },
+        result = &mut secondary => match result {
+            Ok(value) => Ok((value, Leg::Secondary)),
+            Err(_) => primary.await.map(|value| (value, Leg::Primary)),
+        },
+    }
+}
+
+/// Hedged transcription: `secondary` is asked too if `primary` is slower than `delay`.
+/// Returns the transcript with the id of the provider that produced it.
+pub async fn transcribe(
+    primary: &dyn TranscriptionProvider,
+    primary_request: TranscriptionRequest,
+    secondary: &dyn TranscriptionProvider,
+    secondary_request: TranscriptionRequest,
+    delay: Duration,
+) -> ProviderResult<(Transcript, &'static str)> {
+    let (transcript, leg) = race(
+        primary.transcribe(primary_request),
+        secondary.transcribe(secondary_request),
+        delay,
+    )
+    .await?;
+    let winner = match leg {
+        Leg::Primary => primary.id(),
+        Leg::Secondary => secondary.id(),
+    };
+    Ok((transcript, winner))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::settings::Settings;
+    use crate::providers::ProviderCapabilities;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use tokio::time::sleep;
+
+    /// Provider that answers with its id after `after_ms`
+    struct Delayed {
+        id: &'static str,
+        after_ms: u64,
+    }
+
+    #[async_trait]
+    impl TranscriptionProvider for Delayed {
+        fn id(&self) -> &'static str {
+            self.id
+        }
+
+        fn display_name(&self) -> &'static str {
+            self.id
+        }
+
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities { languages: &[], max_upload_bytes: usize::MAX, timestamps: false }
+        }
+
+        fn api_key<'a>(&self, _settings: &'a Settings) -> &'a str {
+            ""
+        }
+
+        async fn transcribe(&self, _request: TranscriptionRequest) -> ProviderResult<Transcript> {
+            sleep(Duration::from_millis(self.after_ms)).await;
+            Ok(Transcript::from_text(self.id))
+        }
+    }
+
+    fn request() -> TranscriptionRequest {
+        TranscriptionRequest {
+            audio: Vec::new(),
+            api_key: String::new(),
+            language: None,
+            vocabulary: Vec::new(),
+            context: None,
+            retry: Default::default(),
+        }
+    }
+
+    async fn respond(after_ms: u64, result: Result<&'static str, &'static str>) -> Result<&'static str, &'static str> {
+        sleep(Duration::from_millis(after_ms)).await;
+        result
+    }
+
+    #[tokio::test]
+    async fn test_fast_primary_never_starts_secondary() {
+        let started = AtomicBool::new(false);
+        let secondary = async {
+            started.store(true, Ordering::SeqCst);
+            Ok("secondary")
+        };
+        let result = race(respond(5, Ok("primary")), secondary, Duration::from_millis(100)).await;
+        assert_eq!(result, Ok(("primary", Leg::Primary)));
+        assert!(!started.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_slow_primary_loses_to_secondary() {
+        let result = race(respond(2_000, Ok("primary")), respond(10, Ok("secondary")), Duration::from_millis(20)).await;
+        assert_eq!(result, Ok(("secondary", Leg::Secondary)));
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_credits_the_faster_provider() {
+        let primary = Delayed { id: "groq", after_ms: 2_000 };
+        let secondary = Delayed { id: "fireworks", after_ms: 10 };
+        let (transcript, winner) = transcribe(&primary, request(), &secondary, request(), Duration::from_millis(20))
+            .await
+            .unwrap();
+        assert_eq!(winner, "fireworks");
+        assert_eq!(transcript.text, "fireworks");
+
+        let primary = Delayed { id: "groq", after_ms: 5 };
+        let (_, winner) = transcribe(&primary, request(), &secondary, request(), Duration::from_millis(100))
+            .await
+            .unwrap();
+        assert_eq!(winner, "groq");
+    }
+
+    #[tokio::test]
+    async fn test_failed_leg_waits_for_the_other() {
+        let result = race(respond(50, Err("primary down")), respond(200, Ok("secondary")), Duration::from_millis(10)).await;
+        assert_eq!(result, Ok(("secondary", Leg::Secondary)));
+
+        let result = race(respond(200, Ok("primary")), respond(5, Err("secondary down")), Duration::from_millis(10)).await;
+        assert_eq!(result, Ok(("primary", Leg::Primary)));
+
+        let result = race(respond(50, Err("primary down")), respond(5, Err("secondary down")), Duration::from_millis(10)).await;
+        assert_eq!(result, Err("primary down"));
+    }
+}
@@ -0,0 +1,49 @@
+use std::sync::OnceLock;
+
+use chrono::Utc;
+use reqwest::header::RETRY_AFTER;
+
+use super::error::{parse_retry_after, ProviderError};
+use super::ProviderResult;
+
+// Reusable HTTP client with connection pooling, shared by every HTTP provider
+static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+pub fn client() -> &'static reqwest::Client {
+    HTTP_CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .pool_max_idle_per_host(10)
+            .pool_idle_timeout(std::time::Duration::from_secs(90))
+            .tcp_keepalive(std::time::Duration::from_secs(60))
+            .http2_keep_alive_interval(std::time::Duration::from_secs(30))
+            .http2_keep_alive_timeout(std::time::Duration::from_secs(20))
+            .build()
+            .expect("Failed to create HTTP client")
+    })
+}
+
+/// Send a request once and map transport failures and non-2xx statuses to provider errors.
+/// Use `retry::send` for requests that should survive transient failures.
+pub async fn send(request: reqwest::RequestBuilder) -> ProviderResult<reqwest::Response> {
+    let response = request.send().await?;
+
+    // Check status code
+    if !response.status().is_success() {
+        return Err(status_error(response).await);
+    }
+
+    Ok(response)
+}
+
+/// Classify a non-2xx response, keeping its body and Retry-After header
+pub async fn status_error(response: reqwest::Response) -> ProviderError {
+    let status = response.status().as_u16();
+    let retry_after = response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| parse_retry_after(value, Utc::now()));
+    let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+    ProviderError::from_status(status, retry_after, error_text)
+}
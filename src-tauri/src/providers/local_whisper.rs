@@ -0,0 +1,304 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use tauri::{AppHandle, Manager};
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+use super::transcript::Segment;
+use super::{vocabulary, ProviderCapabilities, ProviderError, ProviderResult, Transcript, TranscriptionProvider, TranscriptionRequest, WHISPER_LANGUAGES};
+use crate::commands::settings::Settings;
+
+/// Sample rate the frontend encodes VAD segments at (and Whisper expects)
+const SAMPLE_RATE: u32 = 16_000;
+
+/// Most CPU threads one transcription uses
+const MAX_THREADS: usize = 8;
+
+/// Loaded model, kept for the lifetime of the app (loading takes seconds)
+static MODEL: Mutex<Option<(PathBuf, Arc<WhisperContext>)>> = Mutex::new(None);
+
+/// Held while a model loads, so concurrent segments load it once without holding `MODEL`
+static LOADING: Mutex<()> = Mutex::new(());
+
+/// Resolve a ggml model file from the app data dir (`<app data>/models/<file>`),
+/// falling back to models bundled with the app (`resources/models/<file>`)
+pub fn model_path(app: &AppHandle, file_name: &str) -> PathBuf {
+    let data_path = app
+        .path()
+        .resolve(format!("models/{}", file_name), tauri::path::BaseDirectory::AppData);
+    if let Ok(path) = &data_path {
+        if path.exists() {
+            return path.clone();
+        }
+    }
+
+    app.path()
+        .resolve(
+            format!("resources/models/{}", file_name),
+            tauri::path::BaseDirectory::Resource,
+        )
+        .ok()
+        .filter(|path| path.exists())
+        .or(data_path.ok())
+        .unwrap_or_else(|| PathBuf::from(file_name))
+}
+
+/// The loaded model when it is the one at `path`
+fn loaded_context(path: &Path) -> Option<Arc<WhisperContext>> {
+    MODEL
+        .lock()
+        .unwrap()
+        .as_ref()
+        .filter(|(loaded_path, _)| loaded_path == path)
+        .map(|(_, context)| context.clone())
+}
+
+/// Load the model once and reuse it; reloads only when the configured file changes.
+/// Segments using an already loaded model never wait for a load.
+fn load_context(path: &Path) -> ProviderResult<Arc<WhisperContext>> {
+    if let Some(context) = loaded_context(path) {
+        return Ok(context);
+    }
+    let _loading = LOADING.lock().unwrap();
+    // Another segment may have loaded it while we waited
+    if let Some(context) = loaded_context(path) {
+        return Ok(context);
+    }
+
+    if !path.exists() {
+        return Err(format!("Local Whisper model not found: {}", path.display()).into());
+    }
+
+    let path_str = path.to_str().ok_or("Invalid model path")?;
+    let context = Arc::new(
+        WhisperContext::new_with_params(path_str, WhisperContextParameters::default())
+            .map_err(|e| format!("Failed to load local Whisper model: {}", e))?,
+    );
+    *MODEL.lock().unwrap() = Some((path.to_path_buf(), context.clone()));
+    Ok(context)
+}
+
+/// CPU threads per transcription when `parallel_segments` segments run at once
+fn thread_count(parallel_segments: usize) -> usize {
+    let cores = std::thread::available_parallelism().map_or(4, |n| n.get());
+    (cores / parallel_segments.max(1)).clamp(1, MAX_THREADS)
+}
+
+/// Decode a 16 kHz mono PCM16 WAV file into f32 samples in [-1, 1]
+fn decode_wav(data: &[u8]) -> ProviderResult<Vec<f32>> {
+    if data.len() < 12 || &data[0..4] != b"RIFF" || &data[8..12] != b"WAVE" {
+        return Err(ProviderError::BadAudio("Audio is not a WAV file".to_string()));
+    }
+
+    // Walk the chunks looking for "fmt " and "data"
+    let mut offset = 12;
+    let mut format_ok = false;
+    while offset + 8 <= data.len() {
+        let id = &data[offset..offset + 4];
+        let size = u32::from_le_bytes([data[offset + 4], data[offset + 5], data[offset + 6], data[offset + 7]]) as usize;
+        let body_start = offset + 8;
+        let body_end = (body_start + size).min(data.len());
+        let body = &data[body_start..body_end];
+
+        match id {
+            b"fmt " if body.len() >= 16 => {
+                let channels = u16::from_le_bytes([body[2], body[3]]);
+                let sample_rate = u32::from_le_bytes([body[4], body[5], body[6], body[7]]);
+                let bits_per_sample = u16::from_le_bytes([body[14], body[15]]);
+                if channels != 1 || sample_rate != SAMPLE_RATE || bits_per_sample != 16 {
+                    return Err(ProviderError::BadAudio(format!(
+                        "Unsupported WAV format: {} ch, {} Hz, {} bit (expected mono 16 kHz PCM16)",
+                        channels, sample_rate, bits_per_sample
+                    )));
+                }
+                format_ok = true;
+            }
+            b"data" => {
+                if !format_ok {
+                    return Err(ProviderError::BadAudio("WAV data chunk before format chunk".to_string()));
+                }
+                return Ok(body
+                    .chunks_exact(2)
+                    .map(|s| i16::from_le_bytes([s[0], s[1]]) as f32 / 32768.0)
+                    .collect());
+            }
+            _ => {}
+        }
+
+        // Chunks are padded to an even size
+        offset = body_start + size + (size & 1);
+    }
+
+    Err(ProviderError::BadAudio("WAV file has no audio data".to_string()))
+}
+
+/// Run Whisper on CPU (blocking)
+fn run_whisper(
+    context: &WhisperContext,
+    samples: &[f32],
+    language: Option<&str>,
+    prompt: Option<&str>,
+    threads: usize,
+) -> ProviderResult<Transcript> {
+    let mut state = context
+        .create_state()
+        .map_err(|e| format!("Failed to create Whisper state: {}", e))?;
+
+    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+    params.set_n_threads(threads as i32);
+    params.set_language(Some(language.unwrap_or("auto")));
+    params.set_translate(false);
+    // Each VAD segment is independent
+    params.set_no_context(true);
+    params.set_suppress_blank(true);
+    // Custom words and the previous segment
+    if let Some(prompt) = prompt {
+        params.set_initial_prompt(prompt);
+    }
+    params.set_print_special(false);
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_print_timestamps(false);
+
+    state
+        .full(params, samples)
+        .map_err(|e| format!("Local Whisper transcription failed: {}", e))?;
+
+    let failed = |e| ProviderError::Other(format!("Local Whisper transcription failed: {}", e));
+    let count = state.full_n_segments().map_err(failed)?;
+    let mut segments = Vec::new();
+    for segment in 0..count {
+        // Timestamps are in units of 10 ms
+        segments.push(Segment {
+            text: state.full_get_segment_text_lossy(segment).map_err(failed)?,
+            start: state.full_get_segment_t0(segment).map_err(failed)? as f64 / 100.0,
+            end: state.full_get_segment_t1(segment).map_err(failed)? as f64 / 100.0,
+            avg_logprob: None,
+            no_speech_prob: Some(state.full_get_segment_no_speech_prob(segment).map_err(failed)? as f64),
+            compression_ratio: None,
+        });
+    }
+
+    let text: String = segments.iter().map(|segment| segment.text.as_str()).collect();
+    Ok(Transcript {
+        text: text.trim().to_string(),
+        language: language.map(str::to_string),
+        duration: Some(samples.len() as f64 / SAMPLE_RATE as f64),
+        segments,
+        words: Vec::new(),
+    })
+}
+
+// ============================================================================
+// Local Whisper Transcription
+// ============================================================================
+
+/// Whisper running in-process on CPU via whisper.cpp; no network or API key
+pub struct LocalWhisperProvider {
+    model_path: PathBuf,
+    threads: usize,
+}
+
+impl LocalWhisperProvider {
+    /// `parallel_segments` is how many segments may be transcribed at once; they
+    /// share the CPU cores between them
+    pub fn new(model_path: PathBuf, parallel_segments: usize) -> Self {
+        Self {
+            model_path,
+            threads: thread_count(parallel_segments),
+        }
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for LocalWhisperProvider {
+    fn id(&self) -> &'static str {
+        "local"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Local Whisper (offline)"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            languages: WHISPER_LANGUAGES,
+            // No upload; VAD caps segments at 30 seconds anyway
+            max_upload_bytes: 100 * 1024 * 1024,
+            timestamps: true,
+        }
+    }
+
+    fn api_key<'a>(&self, _settings: &'a Settings) -> &'a str {
+        ""
+    }
+
+    fn requires_api_key(&self) -> bool {
+        false
+    }
+
+    async fn transcribe(&self, request: TranscriptionRequest) -> ProviderResult<Transcript> {
+        // Validate audio data
+        if request.audio.len() < 100 {
+            return Err(ProviderError::BadAudio("Audio data too small".to_string()));
+        }
+
+        let model_path = self.model_path.clone();
+        let threads = self.threads;
+        tokio::task::spawn_blocking(move || {
+            let samples = decode_wav(&request.audio)?;
+            let context = load_context(&model_path)?;
+            let prompt = vocabulary::whisper_prompt(&request.vocabulary, request.context.as_deref());
+            run_whisper(&context, &samples, request.language.as_deref(), prompt.as_deref(), threads)
+        })
+        .await?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wav(sample_rate: u32, samples: &[i16]) -> Vec<u8> {
+        let data_len = (samples.len() * 2) as u32;
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+        wav.extend_from_slice(&sample_rate.to_le_bytes());
+        wav.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        for sample in samples {
+            wav.extend_from_slice(&sample.to_le_bytes());
+        }
+        wav
+    }
+
+    #[test]
+    fn test_decode_wav_samples() {
+        let samples = decode_wav(&wav(16_000, &[0, 16384, -32768])).unwrap();
+        assert_eq!(samples, vec![0.0, 0.5, -1.0]);
+    }
+
+    #[test]
+    fn test_decode_wav_rejects_other_rates() {
+        assert!(decode_wav(&wav(44_100, &[0, 1])).is_err());
+        assert!(decode_wav(b"not a wav file").is_err());
+    }
+
+    #[test]
+    fn test_thread_count_shares_cores() {
+        let cores = std::thread::available_parallelism().map_or(4, |n| n.get());
+        assert_eq!(thread_count(1), cores.min(MAX_THREADS));
+        assert!(thread_count(3) * 3 <= cores.max(3));
+        assert_eq!(thread_count(cores + 1), 1);
+        assert_eq!(thread_count(0), thread_count(1));
+    }
+}
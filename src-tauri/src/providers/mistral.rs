@@ -1,22 +1,12 @@
+use async_trait::async_trait;
 use reqwest::multipart;
 use serde::{Deserialize, Serialize};
-use std::sync::OnceLock;
-
-static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
-
-fn get_http_client() -> &'static reqwest::Client {
-    HTTP_CLIENT.get_or_init(|| {
-        reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .pool_max_idle_per_host(10)
-            .pool_idle_timeout(std::time::Duration::from_secs(90))
-            .tcp_keepalive(std::time::Duration::from_secs(60))
-            .http2_keep_alive_interval(std::time::Duration::from_secs(30))
-            .http2_keep_alive_timeout(std::time::Duration::from_secs(20))
-            .build()
-            .expect("Failed to create HTTP client")
-    })
-}
+
+use super::{http, retry, vocabulary, ProviderCapabilities, ProviderError, ProviderResult, RewriteModel, RewriteProvider, RewriteRequest, Transcript, TranscriptionProvider, TranscriptionRequest, VOXTRAL_LANGUAGES};
+use crate::commands::settings::Settings;
+
+/// Voxtral accepts up to 100 `context_bias` words or phrases
+const MAX_CONTEXT_BIAS_TERMS: usize = 100;
 
 // ============================================================================
 // Whisper Transcription Structures
@@ -37,79 +27,93 @@ struct TranscriptionResponse {
 // Whisper Transcription API
 // ============================================================================
 
-/// Transcribe audio using Mistral Voxtral with batch processing
-/// Supports optional language parameter for better accuracy
-pub async fn transcribe_verbose(
-    audio_data: Vec<u8>,
-    api_key: String,
-    language: Option<String>,
-) -> Result<String, Box<dyn std::error::Error>> {
-    if audio_data.len() < 100 {
-        return Err("Audio data too small".into());
+/// Mistral Voxtral batch transcription
+pub struct MistralProvider;
+
+#[async_trait]
+impl TranscriptionProvider for MistralProvider {
+    fn id(&self) -> &'static str {
+        "mistral"
     }
-    
-    let client = get_http_client();
-    
-    let model_name = "voxtral-mini-2602";
-
-    // Create multipart form
-    let part = multipart::Part::bytes(audio_data)
-        .file_name("segment.wav")
-        .mime_str("audio/wav")?;
-
-    let mut form = multipart::Form::new()
-        .part("file", part)
-        .text("model", model_name);
-
-    // Add language if specified
-    if let Some(lang) = language {
-        form = form.text("language", lang);
+
+    fn display_name(&self) -> &'static str {
+        "Mistral Voxtral"
     }
 
-    let response = client
-        .post("https://api.mistral.ai/v1/audio/transcriptions")
-        .header("x-api-key", api_key)  // Mistral uses x-api-key header
-        .multipart(form)
-        .send()
-        .await
-        .map_err(|e| {
-            if e.is_timeout() {
-                "Request timeout".to_string()
-            } else if e.is_connect() {
-                "Connection failed - check internet".to_string()
-            } else {
-                e.to_string()
-            }
-        })?;
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            languages: VOXTRAL_LANGUAGES,
+            max_upload_bytes: 25 * 1024 * 1024,
+            timestamps: true,
+        }
+    }
 
-    // Check status code
-    let status = response.status();
-    if !status.is_success() {
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("API error ({}): {}", status.as_u16(), error_text).into());
+    fn api_key<'a>(&self, settings: &'a Settings) -> &'a str {
+        &settings.mistral_api_key
     }
 
-    let result: TranscriptionResponse = response.json().await?;
+    /// Transcribe audio using Mistral Voxtral with batch processing
+    /// Supports optional language parameter for better accuracy
+    async fn transcribe(&self, request: TranscriptionRequest) -> ProviderResult<Transcript> {
+        if request.audio.len() < 100 {
+            return Err(ProviderError::BadAudio("Audio data too small".to_string()));
+        }
+
+        let model_name = "voxtral-mini-2602";
+
+        // Create multipart form (rebuilt for every retry attempt)
+        let build_form = || -> ProviderResult<multipart::Form> {
+            let part = multipart::Part::bytes(request.audio.clone())
+                .file_name("segment.wav")
+                .mime_str("audio/wav")?;
+
+            let mut form = multipart::Form::new()
+                .part("file", part)
+                .text("model", model_name);
+
+            // Add language if specified
+            if let Some(lang) = &request.language {
+                form = form.text("language", lang.clone());
+            }
+
+            // Bias recognition toward the user's custom words
+            for term in vocabulary::terms(&request.vocabulary, MAX_CONTEXT_BIAS_TERMS, usize::MAX) {
+                form = form.text("context_bias", term);
+            }
 
-    // Try to extract text from response
-    if let Some(txt) = result.text {
-        if !txt.trim().is_empty() {
-            return Ok(txt);
+            Ok(form)
+        };
+
+        let response = retry::send(&request.retry, || {
+            Ok(http::client()
+                .post("https://api.mistral.ai/v1/audio/transcriptions")
+                .header("x-api-key", &request.api_key)  // Mistral uses x-api-key header
+                .multipart(build_form()?))
+        })
+        .await?;
+
+        let result: TranscriptionResponse = response.json().await?;
+
+        // Try to extract text from response
+        if let Some(txt) = result.text {
+            if !txt.trim().is_empty() {
+                return Ok(Transcript::from_text(txt));
+            }
         }
-    }
-    
-    // Fallback: check results array
-    if let Some(results) = result.results {
-        for r in results {
-            if let Some(t) = r.text {
-                if !t.trim().is_empty() {
-                    return Ok(t);
+
+        // Fallback: check results array
+        if let Some(results) = result.results {
+            for r in results {
+                if let Some(t) = r.text {
+                    if !t.trim().is_empty() {
+                        return Ok(Transcript::from_text(t));
+                    }
                 }
             }
         }
-    }
 
-    Err("No text in response".into())
+        Err("No text in response".into())
+    }
 }
 
 // ============================================================================
@@ -124,8 +128,10 @@ struct ChatInput {
 
 #[derive(Debug, Serialize)]
 struct CompletionArgs {
-    temperature: f32,
-    max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
     top_p: f32,
 }
 
@@ -155,77 +161,67 @@ struct ChatResponse {
 // ============================================================================
 
 /// Rewrite text using Mistral conversations API
-/// Used for text rewriting and transformation
-pub async fn rewrite_text(
-    text: String,
-    prompt: String,
-    api_key: String,
-) -> Result<String, Box<dyn std::error::Error>> {
-    // Validate inputs
-    if prompt.trim().is_empty() {
-        return Err("Prompt is required".into());
+pub struct MistralRewriteProvider;
+
+#[async_trait]
+impl RewriteProvider for MistralRewriteProvider {
+    fn id(&self) -> &'static str {
+        "mistral"
     }
-    
-    if text.trim().is_empty() {
-        return Err("Text is required".into());
+
+    fn display_name(&self) -> &'static str {
+        "Mistral Small"
     }
-    
-    let client = get_http_client();
-    
-    // Construct the request body
-    let request_body = ChatRequest {
-        model: "mistral-small-latest".to_string(),
-        inputs: vec![ChatInput {
-            role: "user".to_string(),
-            content: format!("{}\n\n{}", prompt, text),
-        }],
-        tools: vec![],
-        completion_args: CompletionArgs {
-            temperature: 0.2,
-            max_tokens: 1024,
-            top_p: 1.0,
-        },
-        stream: false,
-        instructions: String::new(),
-    };
-    
-    let response = client
-        .post("https://api.mistral.ai/v1/conversations")
-        .header("x-api-key", api_key)  // Mistral uses x-api-key header
-        .header("Content-Type", "application/json")
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| {
-            if e.is_timeout() {
-                "Request timeout".to_string()
-            } else if e.is_connect() {
-                "Connection failed - check internet".to_string()
-            } else {
-                e.to_string()
-            }
-        })?;
-    
-    // Check status code
-    let status = response.status();
-    if !status.is_success() {
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("API error ({}): {}", status.as_u16(), error_text).into());
+
+    fn default_model(&self) -> RewriteModel {
+        RewriteModel::new("mistral-small-latest", Some(0.2), Some(1024))
     }
-    
-    let result: ChatResponse = response.json().await?;
-    
-    // Extract content from outputs array
-    if let Some(outputs) = result.outputs {
-        for output in outputs {
-            if let Some(content) = output.content {
-                let trimmed = content.trim();
-                if !trimmed.is_empty() {
-                    return Ok(trimmed.to_string());
+
+    fn api_key<'a>(&self, settings: &'a Settings) -> &'a str {
+        &settings.mistral_api_key
+    }
+
+    async fn rewrite(&self, request: RewriteRequest) -> ProviderResult<String> {
+        // Construct the request body
+        let request_body = ChatRequest {
+            model: request.model.model,
+            inputs: vec![ChatInput {
+                role: "user".to_string(),
+                content: format!("{}\n\n{}", request.prompt, request.text),
+            }],
+            tools: vec![],
+            completion_args: CompletionArgs {
+                temperature: request.model.temperature,
+                max_tokens: request.model.max_tokens,
+                top_p: 1.0,
+            },
+            stream: false,
+            instructions: String::new(),
+        };
+        
+        let response = retry::send(&request.retry, || {
+            Ok(http::client()
+                .post("https://api.mistral.ai/v1/conversations")
+                .header("x-api-key", &request.api_key)  // Mistral uses x-api-key header
+                .header("Content-Type", "application/json")
+                .json(&request_body))
+        })
+        .await?;
+        
+        let result: ChatResponse = response.json().await?;
+        
+        // Extract content from outputs array
+        if let Some(outputs) = result.outputs {
+            for output in outputs {
+                if let Some(content) = output.content {
+                    let trimmed = content.trim();
+                    if !trimmed.is_empty() {
+                        return Ok(trimmed.to_string());
+                    }
                 }
             }
         }
+        
+        Err("No content in response".into())
     }
-    
-    Err("No content in response".into())
 }
@@ -1,3 +1,22 @@
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::commands::settings::{RetrySettings, RewriteModelSettings, Settings};
+
+// Shared provider infrastructure
+pub mod error;
+pub mod transcript;
+pub mod vocabulary;
+pub mod http;
+pub mod retry;
+pub mod registry;
+pub mod chat;
+pub mod fallback;
+pub mod hedge;
+pub mod streaming;
+pub mod supervisor;
+pub mod warm;
+
 // Batch providers (HTTP-based)
 pub mod groq;
 pub mod sambanova;
@@ -5,9 +24,151 @@ pub mod fireworks;
 pub mod gemini;
 pub mod mistral;
 pub mod inception;
+pub mod custom;
+#[cfg(feature = "local-whisper")]
+pub mod local_whisper;
 
 // Streaming providers (WebSocket-based)
 pub mod deepgram;
 pub mod cartesia;
 pub mod voxtral;
 pub mod elevenlabs;
+
+pub use error::ProviderError;
+pub use transcript::Transcript;
+pub use registry::{RewriteRegistry, TranscriptionRegistry};
+
+pub type ProviderResult<T> = Result<T, ProviderError>;
+
+/// Language codes understood by Whisper-family models
+pub const WHISPER_LANGUAGES: &[&str] = &[
+    "en", "zh", "de", "es", "ru", "ko", "fr", "ja", "pt", "tr", "pl", "ca", "nl", "ar", "sv",
+    "it", "id", "hi", "fi", "vi", "he", "uk", "el", "ms", "cs", "ro", "da", "hu", "ta", "no",
+    "th", "ur", "hr", "bg", "lt", "la", "mi", "ml", "cy", "sk", "te", "fa", "lv", "bn", "sr",
+    "az", "sl", "kn", "et", "mk", "br", "eu", "is", "hy", "ne", "mn", "bs", "kk", "sq", "sw",
+    "gl", "mr", "pa", "si", "km", "sn", "yo", "so", "af", "oc", "ka", "be", "tg", "sd", "gu",
+    "am", "yi", "lo", "uz", "fo", "ht", "ps", "tk", "nn", "mt", "sa", "lb", "my", "bo", "tl",
+    "mg", "as", "tt", "haw", "ln", "ha", "ba", "jw", "su", "yue",
+];
+
+/// Languages supported by Mistral's Voxtral transcription models
+pub const VOXTRAL_LANGUAGES: &[&str] = &[
+    "en", "zh", "hi", "es", "ar", "fr", "pt", "ru", "de", "ja", "ko", "it", "nl",
+];
+
+/// Static capability metadata advertised by a batch transcription provider
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderCapabilities {
+    /// Language codes accepted as a hint. Empty means the provider always auto-detects.
+    pub languages: &'static [&'static str],
+    /// Largest audio upload we send to the provider, in bytes
+    pub max_upload_bytes: usize,
+    /// Whether the API can return segment or word timestamps
+    pub timestamps: bool,
+}
+
+impl ProviderCapabilities {
+    pub fn supports_language(&self, code: &str) -> bool {
+        self.languages.contains(&code)
+    }
+}
+
+/// A single audio segment to transcribe
+pub struct TranscriptionRequest {
+    /// WAV-encoded audio (16 kHz mono PCM16)
+    pub audio: Vec<u8>,
+    pub api_key: String,
+    /// Language hint, already filtered against the provider's capabilities
+    pub language: Option<String>,
+    /// Custom words to bias recognition toward (see `vocabulary`); providers apply their own limits
+    pub vocabulary: Vec<String>,
+    /// Tail of the text transcribed just before this segment in the same recording
+    pub context: Option<String>,
+    /// Retry policy for the provider's HTTP calls
+    pub retry: RetrySettings,
+}
+
+/// Batch (HTTP) speech-to-text provider
+#[async_trait]
+pub trait TranscriptionProvider: Send + Sync {
+    /// Stable identifier stored in `Settings.api_service`
+    fn id(&self) -> &'static str;
+
+    /// Human-readable name shown in the settings window
+    fn display_name(&self) -> &'static str;
+
+    fn capabilities(&self) -> ProviderCapabilities;
+
+    /// API key configured for this provider in settings
+    fn api_key<'a>(&self, settings: &'a Settings) -> &'a str;
+
+    /// False for endpoints that accept unauthenticated requests (self-hosted servers)
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+
+    async fn transcribe(&self, request: TranscriptionRequest) -> ProviderResult<Transcript>;
+}
+
+/// Model and sampling parameters used for a rewrite request
+#[derive(Debug, Clone, Serialize)]
+pub struct RewriteModel {
+    pub model: String,
+    /// None leaves the provider's default
+    pub temperature: Option<f32>,
+    /// None leaves the provider's default
+    pub max_tokens: Option<u32>,
+}
+
+impl RewriteModel {
+    pub fn new(model: &str, temperature: Option<f32>, max_tokens: Option<u32>) -> Self {
+        Self { model: model.to_string(), temperature, max_tokens }
+    }
+
+    /// Apply user overrides from settings on top of the provider defaults
+    pub fn with_overrides(mut self, overrides: &RewriteModelSettings) -> Self {
+        if let Some(model) = overrides.model.as_deref().map(str::trim).filter(|m| !m.is_empty()) {
+            self.model = model.to_string();
+        }
+        if overrides.temperature.is_some() {
+            self.temperature = overrides.temperature;
+        }
+        if overrides.max_tokens.is_some() {
+            self.max_tokens = overrides.max_tokens;
+        }
+        self
+    }
+}
+
+/// Text to rewrite with the instructions and resolved model
+pub struct RewriteRequest {
+    pub text: String,
+    pub prompt: String,
+    pub api_key: String,
+    pub model: RewriteModel,
+    /// Retry policy for the provider's HTTP calls
+    pub retry: RetrySettings,
+}
+
+/// Chat model used to rewrite selected text
+#[async_trait]
+pub trait RewriteProvider: Send + Sync {
+    /// Stable identifier stored in `Settings.rewrite_provider`
+    fn id(&self) -> &'static str;
+
+    /// Human-readable name shown in the settings window
+    fn display_name(&self) -> &'static str;
+
+    /// Model used when settings do not override it
+    fn default_model(&self) -> RewriteModel;
+
+    /// API key configured for this provider in settings
+    fn api_key<'a>(&self, settings: &'a Settings) -> &'a str;
+
+    /// False for endpoints that accept unauthenticated requests (local LLM servers)
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+
+    async fn rewrite(&self, request: RewriteRequest) -> ProviderResult<String>;
+}
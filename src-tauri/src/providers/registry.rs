@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::commands::settings::Settings;
+#[cfg(feature = "local-whisper")]
+use super::local_whisper;
+use super::{custom, fireworks, gemini, groq, inception, mistral, sambanova};
+use super::{ProviderCapabilities, RewriteModel, RewriteProvider, TranscriptionProvider};
+
+/// Provider used when the requested id is unknown
+pub const DEFAULT_TRANSCRIPTION_PROVIDER: &str = "groq";
+
+/// Rewrite provider used when the requested id is unknown
+pub const DEFAULT_REWRITE_PROVIDER: &str = "groq";
+
+/// Provider metadata sent to the settings window
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderInfo {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub capabilities: ProviderCapabilities,
+}
+
+/// Batch transcription providers keyed by id, in registration order
+pub struct TranscriptionRegistry {
+    providers: Vec<Arc<dyn TranscriptionProvider>>,
+}
+
+impl TranscriptionRegistry {
+    /// Registry with every provider that ships with the app
+    pub fn builtin() -> Self {
+        let mut registry = Self { providers: Vec::new() };
+        registry.register(Arc::new(groq::GroqProvider));
+        registry.register(Arc::new(sambanova::SambaNovaProvider));
+        registry.register(Arc::new(fireworks::FireworksProvider));
+        registry.register(Arc::new(gemini::GeminiProvider));
+        registry.register(Arc::new(mistral::MistralProvider));
+        registry
+    }
+
+    /// Built-in providers plus the user-configured custom endpoint
+    pub fn from_settings(settings: &Settings) -> Self {
+        let mut registry = Self::builtin();
+        registry.register(Arc::new(custom::CustomProvider::new(
+            settings.custom_transcription.clone(),
+        )));
+        registry
+    }
+
+    /// Providers available in this build: `from_settings` plus the on-device
+    /// Whisper model when the `local-whisper` feature is enabled
+    #[cfg_attr(not(feature = "local-whisper"), allow(unused_variables))]
+    pub fn for_app(app: &AppHandle, settings: &Settings) -> Self {
+        #[allow(unused_mut)]
+        let mut registry = Self::from_settings(settings);
+        #[cfg(feature = "local-whisper")]
+        registry.register(Arc::new(local_whisper::LocalWhisperProvider::new(
+            local_whisper::model_path(app, &settings.advanced.local_whisper_model),
+            settings.advanced.max_parallel_segments,
+        )));
+        registry
+    }
+
+    /// Add a provider, replacing any existing provider with the same id
+    pub fn register(&mut self, provider: Arc<dyn TranscriptionProvider>) {
+        self.providers.retain(|p| p.id() != provider.id());
+        self.providers.push(provider);
+    }
+
+    pub fn get(&self, id: &str) -> Option<Arc<dyn TranscriptionProvider>> {
+        self.providers.iter().find(|p| p.id() == id).cloned()
+    }
+
+    /// Look up a provider, falling back to the default one for unknown ids
+    pub fn resolve(&self, id: &str) -> Option<Arc<dyn TranscriptionProvider>> {
+        self.get(id).or_else(|| self.get(DEFAULT_TRANSCRIPTION_PROVIDER))
+    }
+
+    pub fn list(&self) -> Vec<ProviderInfo> {
+        self.providers
+            .iter()
+            .map(|p| ProviderInfo {
+                id: p.id(),
+                name: p.display_name(),
+                capabilities: p.capabilities(),
+            })
+            .collect()
+    }
+}
+
+/// Rewrite provider metadata sent to the settings window
+#[derive(Debug, Clone, Serialize)]
+pub struct RewriteProviderInfo {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub default_model: RewriteModel,
+}
+
+/// Text rewrite providers keyed by id, in registration order
+pub struct RewriteRegistry {
+    providers: Vec<Arc<dyn RewriteProvider>>,
+}
+
+impl RewriteRegistry {
+    /// Registry with every rewrite provider that ships with the app
+    pub fn builtin() -> Self {
+        let mut registry = Self { providers: Vec::new() };
+        registry.register(Arc::new(groq::GroqRewriteProvider));
+        registry.register(Arc::new(fireworks::FireworksRewriteProvider));
+        registry.register(Arc::new(sambanova::SambaNovaRewriteProvider));
+        registry.register(Arc::new(gemini::GEMINI_FLASH_LITE));
+        registry.register(Arc::new(gemini::GEMINI_FLASH));
+        registry.register(Arc::new(mistral::MistralRewriteProvider));
+        registry.register(Arc::new(inception::InceptionRewriteProvider));
+        registry
+    }
+
+    /// Built-in providers plus the user-configured custom endpoint
+    pub fn from_settings(settings: &Settings) -> Self {
+        let mut registry = Self::builtin();
+        registry.register(Arc::new(custom::CustomRewriteProvider::new(
+            settings.custom_rewrite_endpoint.clone(),
+        )));
+        registry
+    }
+
+    /// Add a provider, replacing any existing provider with the same id
+    pub fn register(&mut self, provider: Arc<dyn RewriteProvider>) {
+        self.providers.retain(|p| p.id() != provider.id());
+        self.providers.push(provider);
+    }
+
+    pub fn get(&self, id: &str) -> Option<Arc<dyn RewriteProvider>> {
+        self.providers.iter().find(|p| p.id() == id).cloned()
+    }
+
+    /// Look up a provider, falling back to the default one for unknown ids
+    pub fn resolve(&self, id: &str) -> Option<Arc<dyn RewriteProvider>> {
+        self.get(id).or_else(|| self.get(DEFAULT_REWRITE_PROVIDER))
+    }
+
+    pub fn list(&self) -> Vec<RewriteProviderInfo> {
+        self.providers
+            .iter()
+            .map(|p| RewriteProviderInfo {
+                id: p.id(),
+                name: p.display_name(),
+                default_model: p.default_model(),
+            })
+            .collect()
+    }
+}
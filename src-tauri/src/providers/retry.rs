@@ -0,0 +1,92 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use reqwest::{RequestBuilder, Response};
+use tokio::time::Instant;
+
+use super::{http, ProviderError, ProviderResult};
+use crate::commands::settings::RetrySettings;
+
+/// Exponential backoff for the given retry (0-based), capped at `max_delay_ms`.
+/// `jitter` in [0, 1] spreads the delay over the upper half of the window
+/// so concurrent requests do not retry in lockstep.
+fn backoff_delay(policy: &RetrySettings, retry: u32, jitter: f64) -> Duration {
+    let window = policy
+        .initial_delay_ms
+        .saturating_mul(1u64 << retry.min(20))
+        .min(policy.max_delay_ms);
+    Duration::from_millis((window as f64 * (0.5 + jitter.clamp(0.0, 1.0) / 2.0)) as u64)
+}
+
+/// Random value in [0, 1] without pulling in a RNG crate
+fn jitter() -> f64 {
+    RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64
+}
+
+/// Jittered backoff before the given retry (0-based), for retry loops outside `send`
+pub fn backoff(policy: &RetrySettings, retry: u32) -> Duration {
+    backoff_delay(policy, retry, jitter())
+}
+
+/// Send a request, retrying transport failures, rate limits and 5xx responses with
+/// jittered exponential backoff (or the server's Retry-After) until the policy's
+/// retry count or overall deadline runs out.
+/// `make_request` is called once per attempt because multipart bodies cannot be cloned.
+pub async fn send(
+    policy: &RetrySettings,
+    mut make_request: impl FnMut() -> ProviderResult<RequestBuilder>,
+) -> ProviderResult<Response> {
+    let deadline = Instant::now() + Duration::from_millis(policy.deadline_ms);
+    let mut retry = 0;
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let outcome = tokio::time::timeout(remaining, make_request()?.send()).await;
+
+        let error = match outcome {
+            Err(_) => return Err(ProviderError::Timeout),
+            Ok(Ok(response)) if response.status().is_success() => return Ok(response),
+            Ok(Ok(response)) => http::status_error(response).await,
+            Ok(Err(e)) => ProviderError::from(e),
+        };
+        if !error.is_retryable() || retry >= policy.max_retries {
+            return Err(error);
+        }
+
+        // Give up early when the wait would run past the deadline
+        let delay = error.retry_after().unwrap_or_else(|| backoff_delay(policy, retry, jitter()));
+        if Instant::now() + delay >= deadline {
+            return Err(error);
+        }
+
+        retry += 1;
+        eprintln!(
+            "[Retry] {} - retry {}/{} in {} ms",
+            error,
+            retry,
+            policy.max_retries,
+            delay.as_millis()
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_grows_and_caps() {
+        let policy = RetrySettings {
+            max_retries: 5,
+            initial_delay_ms: 500,
+            max_delay_ms: 3000,
+            deadline_ms: 30_000,
+        };
+        assert_eq!(backoff_delay(&policy, 0, 1.0), Duration::from_millis(500));
+        assert_eq!(backoff_delay(&policy, 1, 0.0), Duration::from_millis(500));
+        assert_eq!(backoff_delay(&policy, 2, 1.0), Duration::from_millis(2000));
+        assert_eq!(backoff_delay(&policy, 6, 1.0), Duration::from_millis(3000));
+    }
+}
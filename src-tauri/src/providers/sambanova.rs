@@ -1,22 +1,10 @@
+use async_trait::async_trait;
 use reqwest::multipart;
-use serde::{Deserialize, Serialize};
-use std::sync::OnceLock;
-
-static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
-
-fn get_http_client() -> &'static reqwest::Client {
-    HTTP_CLIENT.get_or_init(|| {
-        reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .pool_max_idle_per_host(10)
-            .pool_idle_timeout(std::time::Duration::from_secs(90))
-            .tcp_keepalive(std::time::Duration::from_secs(60))
-            .http2_keep_alive_interval(std::time::Duration::from_secs(30))
-            .http2_keep_alive_timeout(std::time::Duration::from_secs(20))
-            .build()
-            .expect("Failed to create HTTP client")
-    })
-}
+use serde::Deserialize;
+
+use super::chat::{self, ChatCompletionRequest, ChatMessage};
+use super::{http, retry, vocabulary, ProviderCapabilities, ProviderError, ProviderResult, RewriteModel, RewriteProvider, RewriteRequest, Transcript, TranscriptionProvider, TranscriptionRequest, WHISPER_LANGUAGES};
+use crate::commands::settings::Settings;
 
 // ============================================================================
 // Whisper Transcription Structures
@@ -37,112 +25,94 @@ struct WhisperResponse {
 // Whisper Transcription API
 // ============================================================================
 
-/// Transcribe audio using SambaNova Whisper API with batch processing
-/// Supports optional language parameter for better accuracy
-pub async fn transcribe_verbose(
-    audio_data: Vec<u8>,
-    api_key: String,
-    language: Option<String>,
-) -> Result<String, Box<dyn std::error::Error>> {
-    if audio_data.len() < 100 {
-        return Err("Audio data too small".into());
-    }
-    
-    // SambaNova uses capitalized model name
-    let model_name = "Whisper-Large-v3";
-    
-    let client = get_http_client();
-
-    // Create multipart form
-    let part = multipart::Part::bytes(audio_data)
-        .file_name("audio.wav")
-        .mime_str("audio/wav")?;
-
-    let mut form = multipart::Form::new()
-        .part("file", part)
-        .text("model", model_name)
-        .text("response_format", "json")
-        .text("stream", "false");
-
-    // Add language if specified
-    if let Some(lang) = language {
-        form = form.text("language", lang);
-    }
-
-    let response = client
-        .post("https://api.sambanova.ai/v1/audio/transcriptions")
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("Accept", "application/json")
-        .multipart(form)
-        .send()
-        .await
-        .map_err(|e| {
-            if e.is_timeout() {
-                "Request timeout".to_string()
-            } else if e.is_connect() {
-                "Connection failed - check internet".to_string()
-            } else {
-                e.to_string()
-            }
-        })?;
+/// SambaNova Whisper-Large-v3 batch transcription
+pub struct SambaNovaProvider;
 
-    // Check status code
-    let status = response.status();
-    if !status.is_success() {
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("API error ({}): {}", status.as_u16(), error_text).into());
+#[async_trait]
+impl TranscriptionProvider for SambaNovaProvider {
+    fn id(&self) -> &'static str {
+        "sambanova"
     }
 
-    let result: WhisperResponse = response.json().await?;
+    fn display_name(&self) -> &'static str {
+        "SambaNova Whisper"
+    }
 
-    if let Some(txt) = result.text {
-        if !txt.trim().is_empty() {
-            return Ok(txt);
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            languages: WHISPER_LANGUAGES,
+            max_upload_bytes: 25 * 1024 * 1024,
+            timestamps: false,
         }
     }
-    if let Some(results) = result.results {
-        for r in results {
-            if let Some(t) = r.text {
-                if !t.trim().is_empty() {
-                    return Ok(t);
-                }
-            }
-        }
+
+    fn api_key<'a>(&self, settings: &'a Settings) -> &'a str {
+        &settings.sambanova_api_key
     }
 
-    Err("No text in response".into())
-}
+    /// Transcribe audio using SambaNova Whisper API with batch processing
+    /// Supports optional language parameter for better accuracy
+    async fn transcribe(&self, request: TranscriptionRequest) -> ProviderResult<Transcript> {
+        if request.audio.len() < 100 {
+            return Err(ProviderError::BadAudio("Audio data too small".to_string()));
+        }
 
-// ============================================================================
-// Chat Completion Structures
-// ============================================================================
+        // SambaNova uses capitalized model name
+        let model_name = "Whisper-Large-v3";
 
-#[derive(Debug, Serialize)]
-struct ChatMessage {
-    role: String,
-    content: String,
-}
+        // Create multipart form (rebuilt for every retry attempt)
+        let build_form = || -> ProviderResult<multipart::Form> {
+            let part = multipart::Part::bytes(request.audio.clone())
+                .file_name("audio.wav")
+                .mime_str("audio/wav")?;
 
-#[derive(Debug, Serialize)]
-struct ChatCompletionRequest {
-    model: String,
-    messages: Vec<ChatMessage>,
-    stream: bool,
-}
+            let mut form = multipart::Form::new()
+                .part("file", part)
+                .text("model", model_name)
+                .text("response_format", "json")
+                .text("stream", "false");
 
-#[derive(Debug, Deserialize)]
-struct ChatChoice {
-    message: ChatMessageResponse,
-}
+            // Add language if specified
+            if let Some(lang) = &request.language {
+                form = form.text("language", lang.clone());
+            }
 
-#[derive(Debug, Deserialize)]
-struct ChatMessageResponse {
-    content: String,
-}
+            // Bias recognition toward the user's custom words and the previous segment
+            if let Some(prompt) = vocabulary::whisper_prompt(&request.vocabulary, request.context.as_deref()) {
+                form = form.text("prompt", prompt);
+            }
 
-#[derive(Debug, Deserialize)]
-struct ChatCompletionResponse {
-    choices: Vec<ChatChoice>,
+            Ok(form)
+        };
+
+        let response = retry::send(&request.retry, || {
+            Ok(http::client()
+                .post("https://api.sambanova.ai/v1/audio/transcriptions")
+                .header("Authorization", format!("Bearer {}", request.api_key))
+                .header("Accept", "application/json")
+                .multipart(build_form()?))
+        })
+        .await?;
+
+        let result: WhisperResponse = response.json().await?;
+
+        if let Some(txt) = result.text {
+            if !txt.trim().is_empty() {
+                return Ok(Transcript::from_text(txt));
+            }
+        }
+        if let Some(results) = result.results {
+            for r in results {
+                if let Some(t) = r.text {
+                    if !t.trim().is_empty() {
+                        return Ok(Transcript::from_text(t));
+                    }
+                }
+            }
+        }
+
+        Err("No text in response".into())
+    }
 }
 
 // ============================================================================
@@ -150,67 +120,38 @@ struct ChatCompletionResponse {
 // ============================================================================
 
 /// Rewrite text using SambaNova chat completions API
-/// Used for text rewriting and transformation
-pub async fn rewrite_text(
-    text: String,
-    prompt: String,
-    api_key: String,
-) -> Result<String, Box<dyn std::error::Error>> {
-    // Validate inputs
-    if prompt.trim().is_empty() {
-        return Err("Prompt is required".into());
+pub struct SambaNovaRewriteProvider;
+
+#[async_trait]
+impl RewriteProvider for SambaNovaRewriteProvider {
+    fn id(&self) -> &'static str {
+        "sambanova"
     }
-    
-    if text.trim().is_empty() {
-        return Err("Text is required".into());
+
+    fn display_name(&self) -> &'static str {
+        "SambaNova Llama-3.3-70B"
     }
-    
-    let client = get_http_client();
-    
-    // Construct the request body
-    let request_body = ChatCompletionRequest {
-        model: "Meta-Llama-3.3-70B-Instruct".to_string(),
-        messages: vec![ChatMessage {
-            role: "user".to_string(),
-            content: format!("{}\n\n{}", prompt, text),
-        }],
-        stream: false,
-    };
-    
-    let response = client
-        .post("https://api.sambanova.ai/v1/chat/completions")
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("Content-Type", "application/json")
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| {
-            if e.is_timeout() {
-                "Request timeout".to_string()
-            } else if e.is_connect() {
-                "Connection failed - check internet".to_string()
-            } else {
-                e.to_string()
-            }
-        })?;
-    
-    // Check status code
-    let status = response.status();
-    if !status.is_success() {
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("API error ({}): {}", status.as_u16(), error_text).into());
+
+    fn default_model(&self) -> RewriteModel {
+        RewriteModel::new("Meta-Llama-3.3-70B-Instruct", None, None)
     }
-    
-    let result: ChatCompletionResponse = response.json().await?;
-    
-    // Extract content from first choice
-    if let Some(choice) = result.choices.first() {
-        let content = choice.message.content.trim();
-        if !content.is_empty() {
-            return Ok(content.to_string());
-        }
+
+    fn api_key<'a>(&self, settings: &'a Settings) -> &'a str {
+        &settings.sambanova_api_key
     }
-    
-    Err("No content in response".into())
-}
 
+    async fn rewrite(&self, request: RewriteRequest) -> ProviderResult<String> {
+        let request_body = ChatCompletionRequest::new(
+            &request.model,
+            vec![ChatMessage::user(format!("{}\n\n{}", request.prompt, request.text))],
+        );
+
+        chat::complete(
+            "https://api.sambanova.ai/v1/chat/completions",
+            &request.api_key,
+            &request_body,
+            &request.retry,
+        )
+        .await
+    }
+}
@@ -0,0 +1,182 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+
+use crate::commands::settings::Settings;
+
+use super::{cartesia, deepgram, elevenlabs, voxtral, ProviderError, ProviderResult};
+
+// ============================================================================
+// Streaming Session Types
+// ============================================================================
+
+/// Event emitted by a streaming provider for the lifetime of a session
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEvent {
+    /// Interim hypothesis, replaced by the next partial or final
+    Partial(String),
+    /// Committed text (a word delta for providers with `emits_word_deltas`)
+    Final(String),
+    /// Provider detected the start of speech
+    SpeechStarted,
+    /// Provider detected the end of an utterance
+    UtteranceEnd,
+    /// Server or transport error; the session may still be usable
+    Error(ProviderError),
+    /// The socket dropped and reconnect attempt `attempt` (1-based) is pending
+    Reconnecting { attempt: u32 },
+    /// A new socket is open and buffered audio has been replayed
+    Reconnected,
+    /// The socket is closed and no further events will arrive
+    Closed,
+}
+
+/// Connection parameters shared by all streaming providers
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamConfig {
+    pub api_key: String,
+    /// Language code, or None for auto-detect
+    pub language: Option<String>,
+    pub smart_format: bool,
+    /// Audio encoding for providers that accept several (Deepgram)
+    pub encoding: Option<String>,
+    /// Custom words to bias recognition toward (see `vocabulary`)
+    pub vocabulary: Vec<String>,
+}
+
+impl StreamConfig {
+    /// Parameters for a session with `provider`, shared by session start and pre-warming so
+    /// a warm socket matches. `language` is the setting value ("multilingual", "multi" or a
+    /// code); a missing `encoding` falls back to the provider's.
+    pub fn for_provider(
+        provider: &dyn StreamingProvider,
+        api_key: String,
+        language: &str,
+        smart_format: bool,
+        encoding: Option<String>,
+        vocabulary: Vec<String>,
+    ) -> Self {
+        Self {
+            api_key,
+            language: provider.stream_language(language),
+            smart_format,
+            encoding: encoding.or_else(|| provider.encoding().map(str::to_string)),
+            vocabulary,
+        }
+    }
+}
+
+/// Message accepted by a provider's send task
+#[derive(Debug)]
+pub enum SinkMessage {
+    Audio(Vec<u8>),
+    /// Keep an idle socket open (Deepgram KeepAlive, a WebSocket ping elsewhere)
+    KeepAlive,
+    /// Flush pending audio and close the socket using the provider's protocol
+    Close,
+}
+
+/// Write half of a streaming session
+#[derive(Clone)]
+pub struct AudioSink {
+    tx: mpsc::Sender<SinkMessage>,
+}
+
+impl AudioSink {
+    pub async fn send(&self, audio: Vec<u8>) -> ProviderResult<()> {
+        self.tx
+            .send(SinkMessage::Audio(audio))
+            .await
+            .map_err(|_| "Streaming connection is closed".into())
+    }
+
+    /// Keep the socket open while no audio is flowing
+    pub async fn keep_alive(&self) -> ProviderResult<()> {
+        self.tx
+            .send(SinkMessage::KeepAlive)
+            .await
+            .map_err(|_| "Streaming connection is closed".into())
+    }
+
+    /// Ask the provider to finish the stream. Remaining events still arrive on the receiver.
+    pub async fn close(&self) {
+        let _ = self.tx.send(SinkMessage::Close).await;
+    }
+}
+
+/// An open streaming session: an audio sink plus a single event stream
+pub struct StreamSession {
+    pub sink: AudioSink,
+    pub events: mpsc::Receiver<StreamEvent>,
+}
+
+/// Channels a provider's send and receive tasks are wired to
+pub struct StreamChannels {
+    pub audio_rx: mpsc::Receiver<SinkMessage>,
+    pub events_tx: mpsc::Sender<StreamEvent>,
+}
+
+impl StreamSession {
+    /// Create a session and the channel ends its provider tasks should own
+    pub fn channel() -> (Self, StreamChannels) {
+        let (audio_tx, audio_rx) = mpsc::channel::<SinkMessage>(100);
+        let (events_tx, events_rx) = mpsc::channel::<StreamEvent>(100);
+        (
+            Self {
+                sink: AudioSink { tx: audio_tx },
+                events: events_rx,
+            },
+            StreamChannels { audio_rx, events_tx },
+        )
+    }
+}
+
+// ============================================================================
+// Streaming Provider Trait
+// ============================================================================
+
+/// Real-time (WebSocket) speech-to-text provider
+#[async_trait]
+pub trait StreamingProvider: Send + Sync {
+    /// Stable identifier stored in `Settings.api_service`
+    fn id(&self) -> &'static str;
+
+    /// Human-readable name used in error messages
+    fn display_name(&self) -> &'static str;
+
+    /// This provider's key in settings
+    fn api_key<'a>(&self, settings: &'a Settings) -> &'a str;
+
+    /// Audio encoding the frontend streams to this provider (None: provider default)
+    fn encoding(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Language to request for a transcription language setting (None: auto-detect)
+    fn stream_language(&self, language: &str) -> Option<String> {
+        match language {
+            "" | "multi" | "multilingual" => None,
+            code => Some(code.to_string()),
+        }
+    }
+
+    /// True when `Final` events carry word fragments rather than whole utterances
+    fn emits_word_deltas(&self) -> bool {
+        false
+    }
+
+    /// Open the socket and complete the provider's handshake
+    async fn connect(&self, config: StreamConfig) -> ProviderResult<StreamSession>;
+}
+
+/// Look up a streaming provider by id
+pub fn provider(id: &str) -> Option<Arc<dyn StreamingProvider>> {
+    match id {
+        "deepgram" => Some(Arc::new(deepgram::DeepgramProvider)),
+        "cartesia" => Some(Arc::new(cartesia::CartesiaProvider)),
+        "elevenlabs" => Some(Arc::new(elevenlabs::ElevenLabsProvider)),
+        "voxtral" => Some(Arc::new(voxtral::VoxtralProvider)),
+        _ => None,
+    }
+}
@@ -0,0 +1,350 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use super::retry;
+use super::streaming::{SinkMessage, StreamChannels, StreamConfig, StreamEvent, StreamSession, StreamingProvider};
+use super::ProviderError;
+use crate::commands::settings::RetrySettings;
+
+/// Audio kept for replay after a reconnect: 10 seconds of 16 kHz PCM16
+const REPLAY_BUFFER_BYTES: usize = 16_000 * 2 * 10;
+
+/// Audio kept after a committed transcript: 3 seconds the provider may still be
+/// working through when the socket drops
+const REPLAY_TAIL_BYTES: usize = 16_000 * 2 * 3;
+
+/// Reconnect attempts after a dropped socket, spread over at most 30 seconds
+const RECONNECT_POLICY: RetrySettings = RetrySettings {
+    max_retries: 5,
+    initial_delay_ms: 250,
+    max_delay_ms: 4_000,
+    deadline_ms: 30_000,
+};
+
+/// Bounded FIFO of the most recent audio chunks
+struct ReplayBuffer {
+    chunks: VecDeque<Vec<u8>>,
+    bytes: usize,
+    capacity: usize,
+}
+
+impl ReplayBuffer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            chunks: VecDeque::new(),
+            bytes: 0,
+            capacity,
+        }
+    }
+
+    /// Append a chunk, dropping the oldest ones once over capacity
+    fn push(&mut self, chunk: Vec<u8>) {
+        self.bytes += chunk.len();
+        self.chunks.push_back(chunk);
+        self.truncate(self.capacity);
+    }
+
+    /// Drop the oldest chunks until at most `max_bytes` remain
+    fn truncate(&mut self, max_bytes: usize) {
+        while self.bytes > max_bytes {
+            match self.chunks.pop_front() {
+                Some(oldest) => self.bytes -= oldest.len(),
+                None => break,
+            }
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &Vec<u8>> {
+        self.chunks.iter()
+    }
+}
+
+/// Start a streaming session and keep it alive across dropped sockets.
+/// The session is returned immediately: audio sent while the provider handshake is
+/// still running is buffered and flushed in order once the socket is ready, so the
+/// first words are not clipped. When the provider closes unexpectedly it is
+/// reconnected with backoff and the audio the provider may not have transcribed
+/// yet is replayed. A failed handshake arrives as an `Error` event followed by `Closed`.
+/// `warm` is an already open session for the same provider and config (see `warm::WarmPool`).
+pub fn connect(provider: Arc<dyn StreamingProvider>, config: StreamConfig, warm: Option<StreamSession>) -> StreamSession {
+    let (session, channels) = StreamSession::channel();
+    let StreamChannels { audio_rx, events_tx } = channels;
+
+    let supervisor = Supervisor {
+        provider,
+        config,
+        audio_rx,
+        events_tx,
+        replay: ReplayBuffer::new(REPLAY_BUFFER_BYTES),
+        closing: false,
+        pending_error: None,
+    };
+    tokio::spawn(supervisor.run(warm));
+
+    session
+}
+
+struct Supervisor {
+    provider: Arc<dyn StreamingProvider>,
+    config: StreamConfig,
+    /// Audio from the frontend-facing sink
+    audio_rx: mpsc::Receiver<SinkMessage>,
+    /// Events to the frontend-facing session
+    events_tx: mpsc::Sender<StreamEvent>,
+    replay: ReplayBuffer,
+    /// The frontend asked to close; a closed socket is then expected
+    closing: bool,
+    /// Why the last (re)connect failed, reported when the session ends
+    pending_error: Option<ProviderError>,
+}
+
+impl Supervisor {
+    async fn run(mut self, warm: Option<StreamSession>) {
+        let provider = self.provider.clone();
+        let config = self.config.clone();
+        let connected = match warm {
+            Some(session) => Ok(session),
+            None => self.buffer_while(async move { provider.connect(config).await }).await,
+        };
+        let mut inner = match connected {
+            Ok(session) => {
+                self.flush_into(&session).await;
+                session
+            }
+            Err(e) => {
+                eprintln!("[Streaming] Failed to start {}: {}", self.provider.display_name(), e);
+                self.pending_error = Some(e);
+                return self.finish().await;
+            }
+        };
+
+        loop {
+            tokio::select! {
+                message = self.audio_rx.recv(), if !self.closing => match message {
+                    Some(SinkMessage::Audio(audio)) => {
+                        self.replay.push(audio.clone());
+                        // A failed send means the socket is gone; the event side reports it
+                        let _ = inner.sink.send(audio).await;
+                    }
+                    Some(SinkMessage::KeepAlive) => {
+                        let _ = inner.sink.keep_alive().await;
+                    }
+                    Some(SinkMessage::Close) | None => {
+                        self.closing = true;
+                        inner.sink.close().await;
+                    }
+                },
+                event = inner.events.recv() => match event.unwrap_or(StreamEvent::Closed) {
+                    StreamEvent::Closed if !self.closing => {
+                        eprintln!("[Streaming] {} disconnected", self.provider.id());
+                        match self.reconnect().await {
+                            Some(session) => inner = session,
+                            None => break,
+                        }
+                    }
+                    StreamEvent::Closed => break,
+                    event => {
+                        // Finals carry no timestamps, so keep the audio the provider
+                        // may not have transcribed yet in case the socket drops next
+                        if matches!(event, StreamEvent::Final(_)) {
+                            self.replay.truncate(REPLAY_TAIL_BYTES);
+                        }
+                        if self.events_tx.send(event).await.is_err() {
+                            return;
+                        }
+                    }
+                },
+            }
+        }
+
+        self.finish().await;
+    }
+
+    /// Report any held-back error, then end the session
+    async fn finish(&mut self) {
+        if let Some(error) = self.pending_error.take() {
+            let _ = self.events_tx.send(StreamEvent::Error(error)).await;
+        }
+        let _ = self.events_tx.send(StreamEvent::Closed).await;
+    }
+
+    /// Send the buffered audio to a freshly opened socket, in order
+    async fn flush_into(&self, session: &StreamSession) {
+        for chunk in self.replay.iter() {
+            let _ = session.sink.send(chunk.clone()).await;
+        }
+        if self.closing {
+            session.sink.close().await;
+        }
+    }
+
+    /// Reconnect with backoff and replay buffered audio. None when the provider stays
+    /// unreachable or rejects the connection (the error is kept in `pending_error`).
+    async fn reconnect(&mut self) -> Option<StreamSession> {
+        let deadline = Instant::now() + Duration::from_millis(RECONNECT_POLICY.deadline_ms);
+
+        for attempt in 1..=RECONNECT_POLICY.max_retries {
+            let delay = retry::backoff(&RECONNECT_POLICY, attempt - 1);
+            if Instant::now() + delay >= deadline {
+                break;
+            }
+            if self.events_tx.send(StreamEvent::Reconnecting { attempt }).await.is_err() {
+                return None;
+            }
+            self.buffer_while(tokio::time::sleep(delay)).await;
+
+            let provider = self.provider.clone();
+            let config = self.config.clone();
+            match self.buffer_while(async move { provider.connect(config).await }).await {
+                Ok(session) => {
+                    eprintln!(
+                        "[Streaming] {} reconnected, replaying {} bytes",
+                        self.provider.id(),
+                        self.replay.bytes
+                    );
+                    self.flush_into(&session).await;
+                    self.pending_error = None;
+                    let _ = self.events_tx.send(StreamEvent::Reconnected).await;
+                    return Some(session);
+                }
+                Err(e) => {
+                    eprintln!("[Streaming] {} reconnect {} failed: {}", self.provider.id(), attempt, e);
+                    let retryable = e.is_retryable();
+                    self.pending_error = Some(e);
+                    if !retryable {
+                        break;
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Drive `future` to completion while still accepting audio into the replay buffer
+    async fn buffer_while<F: Future>(&mut self, future: F) -> F::Output {
+        tokio::pin!(future);
+        loop {
+            tokio::select! {
+                output = &mut future => return output,
+                message = self.audio_rx.recv(), if !self.closing => match message {
+                    Some(SinkMessage::Audio(audio)) => self.replay.push(audio),
+                    // Nothing to keep alive while (re)connecting
+                    Some(SinkMessage::KeepAlive) => {}
+                    Some(SinkMessage::Close) | None => self.closing = true,
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    use crate::commands::settings::Settings;
+    use crate::providers::ProviderResult;
+
+    #[test]
+    fn test_replay_buffer_drops_oldest() {
+        let mut buffer = ReplayBuffer::new(6);
+        buffer.push(vec![1, 1]);
+        buffer.push(vec![2, 2]);
+        buffer.push(vec![3, 3, 3]);
+        assert_eq!(buffer.iter().cloned().collect::<Vec<_>>(), vec![vec![2, 2], vec![3, 3, 3]]);
+        assert_eq!(buffer.bytes, 5);
+
+        buffer.truncate(3);
+        assert_eq!(buffer.iter().cloned().collect::<Vec<_>>(), vec![vec![3, 3, 3]]);
+        buffer.truncate(0);
+        assert_eq!(buffer.iter().count(), 0);
+        assert_eq!(buffer.bytes, 0);
+    }
+
+    /// Hands each opened session's provider-side channels to the test
+    struct Scripted {
+        opened: mpsc::UnboundedSender<StreamChannels>,
+    }
+
+    #[async_trait]
+    impl StreamingProvider for Scripted {
+        fn id(&self) -> &'static str {
+            "scripted"
+        }
+
+        fn display_name(&self) -> &'static str {
+            "Scripted"
+        }
+
+        fn api_key<'a>(&self, settings: &'a Settings) -> &'a str {
+            &settings.deepgram_api_key
+        }
+
+        async fn connect(&self, _config: StreamConfig) -> ProviderResult<StreamSession> {
+            let (session, channels) = StreamSession::channel();
+            let _ = self.opened.send(channels);
+            Ok(session)
+        }
+    }
+
+    fn config() -> StreamConfig {
+        StreamConfig {
+            api_key: String::new(),
+            language: None,
+            smart_format: false,
+            encoding: None,
+            vocabulary: Vec::new(),
+        }
+    }
+
+    async fn next_audio(channels: &mut StreamChannels) -> Vec<u8> {
+        match channels.audio_rx.recv().await {
+            Some(SinkMessage::Audio(audio)) => audio,
+            other => panic!("expected audio, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replays_audio_sent_after_a_final() {
+        let (opened_tx, mut opened) = mpsc::unbounded_channel();
+        let mut session = connect(Arc::new(Scripted { opened: opened_tx }), config(), None);
+        let mut first = opened.recv().await.unwrap();
+
+        // Committed before the Final and older than the kept tail
+        session.sink.send(vec![0; REPLAY_TAIL_BYTES]).await.unwrap();
+        session.sink.send(vec![1, 1]).await.unwrap();
+        next_audio(&mut first).await;
+        next_audio(&mut first).await;
+        first.events_tx.send(StreamEvent::Final("hello".into())).await.unwrap();
+        assert_eq!(session.events.recv().await, Some(StreamEvent::Final("hello".into())));
+
+        // In flight when the socket drops right after the Final
+        session.sink.send(vec![2, 2]).await.unwrap();
+        next_audio(&mut first).await;
+        drop(first);
+
+        assert_eq!(session.events.recv().await, Some(StreamEvent::Reconnecting { attempt: 1 }));
+        assert_eq!(session.events.recv().await, Some(StreamEvent::Reconnected));
+        let mut second = opened.recv().await.unwrap();
+        assert_eq!(next_audio(&mut second).await, vec![1, 1]);
+        assert_eq!(next_audio(&mut second).await, vec![2, 2]);
+        assert!(second.audio_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reports_errors_right_away() {
+        let (opened_tx, mut opened) = mpsc::unbounded_channel();
+        let mut session = connect(Arc::new(Scripted { opened: opened_tx }), config(), None);
+        let channels = opened.recv().await.unwrap();
+
+        let error = ProviderError::from("rate limited");
+        channels.events_tx.send(StreamEvent::Error(error.clone())).await.unwrap();
+        assert_eq!(session.events.recv().await, Some(StreamEvent::Error(error)));
+    }
+}
@@ -0,0 +1,251 @@
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Transcript Types
+// ============================================================================
+
+/// A recognized word and its position in the audio, in seconds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Word {
+    pub word: String,
+    pub start: f64,
+    pub end: f64,
+    /// Probability in [0, 1]; taken from the enclosing segment when the provider has no per-word value
+    pub confidence: Option<f64>,
+}
+
+/// A run of text the model decoded in one window, in seconds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Segment {
+    pub text: String,
+    pub start: f64,
+    pub end: f64,
+    /// Mean token log probability (Whisper `avg_logprob`)
+    pub avg_logprob: Option<f64>,
+    /// Probability that the window holds no speech (Whisper `no_speech_prob`)
+    pub no_speech_prob: Option<f64>,
+    /// Text compression ratio; high values indicate repetition loops
+    pub compression_ratio: Option<f64>,
+}
+
+impl Segment {
+    /// Average token probability in [0, 1], derived from `avg_logprob`
+    pub fn confidence(&self) -> Option<f64> {
+        self.avg_logprob.map(|logprob| logprob.exp().clamp(0.0, 1.0))
+    }
+}
+
+/// Result of a batch transcription. Providers that only return text leave
+/// `segments` and `words` empty.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Transcript {
+    pub text: String,
+    /// Language reported by the provider, if any
+    pub language: Option<String>,
+    /// Audio duration in seconds, if reported
+    pub duration: Option<f64>,
+    pub segments: Vec<Segment>,
+    pub words: Vec<Word>,
+}
+
+impl Transcript {
+    /// Transcript with text only (no timing or confidence)
+    pub fn from_text(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    /// Render the segments as SubRip subtitles. Without segments the whole text
+    /// becomes a single cue spanning the reported duration.
+    pub fn to_srt(&self) -> String {
+        let cues: Vec<(f64, f64, &str)> = if self.segments.is_empty() {
+            if self.is_empty() {
+                return String::new();
+            }
+            vec![(0.0, self.duration.unwrap_or(0.0), self.text.trim())]
+        } else {
+            self.segments
+                .iter()
+                .filter(|segment| !segment.text.trim().is_empty())
+                .map(|segment| (segment.start, segment.end, segment.text.trim()))
+                .collect()
+        };
+
+        let mut srt = String::new();
+        for (index, (start, end, text)) in cues.into_iter().enumerate() {
+            srt.push_str(&format!(
+                "{}\n{} --> {}\n{}\n\n",
+                index + 1,
+                srt_timestamp(start),
+                srt_timestamp(end),
+                text
+            ));
+        }
+        srt
+    }
+}
+
+/// Format seconds as `HH:MM:SS,mmm`
+fn srt_timestamp(seconds: f64) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+// ============================================================================
+// OpenAI verbose_json Response
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct VerboseSegment {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    start: f64,
+    #[serde(default)]
+    end: f64,
+    avg_logprob: Option<f64>,
+    no_speech_prob: Option<f64>,
+    compression_ratio: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VerboseWord {
+    word: String,
+    start: f64,
+    end: f64,
+    /// Sent by faster-whisper based servers
+    probability: Option<f64>,
+}
+
+/// `verbose_json` transcription response shared by OpenAI-compatible APIs
+/// (Groq, Fireworks, self-hosted Whisper servers). Every field is optional.
+#[derive(Debug, Default, Deserialize)]
+pub struct VerboseJson {
+    text: Option<String>,
+    language: Option<String>,
+    duration: Option<f64>,
+    segments: Option<Vec<VerboseSegment>>,
+    words: Option<Vec<VerboseWord>>,
+}
+
+impl From<VerboseJson> for Transcript {
+    fn from(response: VerboseJson) -> Self {
+        let segments: Vec<Segment> = response
+            .segments
+            .unwrap_or_default()
+            .into_iter()
+            .map(|segment| Segment {
+                text: segment.text,
+                start: segment.start,
+                end: segment.end,
+                avg_logprob: segment.avg_logprob,
+                no_speech_prob: segment.no_speech_prob,
+                compression_ratio: segment.compression_ratio,
+            })
+            .collect();
+
+        let words = response
+            .words
+            .unwrap_or_default()
+            .into_iter()
+            .map(|word| {
+                let confidence = word.probability.or_else(|| {
+                    let midpoint = (word.start + word.end) / 2.0;
+                    segments
+                        .iter()
+                        .find(|segment| segment.start <= midpoint && midpoint <= segment.end)
+                        .and_then(Segment::confidence)
+                });
+                Word {
+                    word: word.word.trim().to_string(),
+                    start: word.start,
+                    end: word.end,
+                    confidence,
+                }
+            })
+            .collect();
+
+        // Some servers leave `text` empty and only fill the segments
+        let text = match response.text {
+            Some(text) if !text.trim().is_empty() => text,
+            _ => segments
+                .iter()
+                .map(|segment| segment.text.trim())
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join(" "),
+        };
+
+        Self {
+            text,
+            language: response.language,
+            duration: response.duration,
+            segments,
+            words,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VERBOSE: &str = r#"{
+        "task": "transcribe",
+        "language": "english",
+        "duration": 3.5,
+        "text": " Hello world. Again.",
+        "segments": [
+            {"id": 0, "start": 0.0, "end": 2.0, "text": " Hello world.", "avg_logprob": -0.1, "no_speech_prob": 0.01, "compression_ratio": 0.9},
+            {"id": 1, "start": 2.0, "end": 3.5, "text": " Again.", "avg_logprob": -1.5, "no_speech_prob": 0.2, "compression_ratio": 0.8}
+        ],
+        "words": [
+            {"word": "Hello", "start": 0.1, "end": 0.6},
+            {"word": "world", "start": 0.7, "end": 1.2, "probability": 0.5},
+            {"word": "Again", "start": 2.2, "end": 2.9}
+        ]
+    }"#;
+
+    #[test]
+    fn test_verbose_json_to_transcript() {
+        let response: VerboseJson = serde_json::from_str(VERBOSE).unwrap();
+        let transcript = Transcript::from(response);
+
+        assert_eq!(transcript.text, " Hello world. Again.");
+        assert_eq!(transcript.language.as_deref(), Some("english"));
+        assert_eq!(transcript.segments.len(), 2);
+        assert_eq!(transcript.segments[1].no_speech_prob, Some(0.2));
+        assert_eq!(transcript.words.len(), 3);
+        // Inherited from segment 0, reported directly, inherited from segment 1
+        assert!((transcript.words[0].confidence.unwrap() - (-0.1f64).exp()).abs() < 1e-9);
+        assert_eq!(transcript.words[1].confidence, Some(0.5));
+        assert!((transcript.words[2].confidence.unwrap() - (-1.5f64).exp()).abs() < 1e-9);
+
+        // Plain json responses still parse
+        let plain: VerboseJson = serde_json::from_str(r#"{"text": "hi"}"#).unwrap();
+        assert_eq!(Transcript::from(plain), Transcript::from_text("hi"));
+    }
+
+    #[test]
+    fn test_to_srt() {
+        let response: VerboseJson = serde_json::from_str(VERBOSE).unwrap();
+        assert_eq!(
+            Transcript::from(response).to_srt(),
+            "1\n00:00:00,000 --> 00:00:02,000\nHello world.\n\n2\n00:00:02,000 --> 00:00:03,500\nAgain.\n\n"
+        );
+        assert_eq!(srt_timestamp(3723.25), "01:02:03,250");
+        assert_eq!(Transcript::from_text("  ").to_srt(), "");
+    }
+}
@@ -0,0 +1,117 @@
+use crate::commands::settings::Settings;
+use crate::services::segment_context;
+
+/// Character budget for a Whisper `prompt`; the model reads at most 224 prompt tokens
+pub const WHISPER_PROMPT_CHARS: usize = 600;
+
+/// Custom words sent to providers to bias recognition, in the user's order.
+/// Empty when vocabulary biasing is turned off; independent of word correction.
+pub fn from_settings(settings: &Settings) -> Vec<String> {
+    if !settings.vocabulary_biasing {
+        return Vec::new();
+    }
+    terms(&settings.custom_words, usize::MAX, usize::MAX)
+}
+
+/// Trimmed, case-insensitively deduplicated terms, stopping before `max_terms`
+/// terms or `max_chars` characters (joined with ", ") would be exceeded
+pub fn terms(words: &[String], max_terms: usize, max_chars: usize) -> Vec<String> {
+    let mut selected: Vec<String> = Vec::new();
+    let mut chars = 0;
+    for word in words.iter().map(|word| word.trim()).filter(|word| !word.is_empty()) {
+        if selected.len() >= max_terms {
+            break;
+        }
+        if selected.iter().any(|existing| existing.to_lowercase() == word.to_lowercase()) {
+            continue;
+        }
+        let added = word.chars().count() + if selected.is_empty() { 0 } else { 2 };
+        if chars + added > max_chars {
+            break;
+        }
+        chars += added;
+        selected.push(word.to_string());
+    }
+    selected
+}
+
+/// Whisper-style prompt listing the terms, within `max_chars`. None without terms.
+/// Whisper continues the style of its prompt, so a plain glossary line works best.
+pub fn prompt(words: &[String], max_chars: usize) -> Option<String> {
+    const PREFIX: &str = "Glossary: ";
+    let selected = terms(words, usize::MAX, max_chars.saturating_sub(PREFIX.len() + 1));
+    if selected.is_empty() {
+        return None;
+    }
+    Some(format!("{}{}.", PREFIX, selected.join(", ")))
+}
+
+/// Whisper `prompt` for a segment: the glossary, then the text spoken just before it.
+/// Whisper reads the prompt as preceding text, so the context goes last; it gets at
+/// most half of the budget and the glossary fills the rest.
+pub fn whisper_prompt(words: &[String], context: Option<&str>) -> Option<String> {
+    let context = context
+        .map(|context| segment_context::tail(context, WHISPER_PROMPT_CHARS / 2))
+        .filter(|context| !context.is_empty());
+    let context_chars = context.map_or(0, |context| context.chars().count() + 1);
+    let glossary = prompt(words, WHISPER_PROMPT_CHARS - context_chars);
+
+    match (glossary, context) {
+        (Some(glossary), Some(context)) => Some(format!("{} {}", glossary, context)),
+        (Some(glossary), None) => Some(glossary),
+        (None, Some(context)) => Some(context.to_string()),
+        (None, None) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_terms_within_limits() {
+        let list = words(&[" Tauri ", "", "Deepgram", "tauri", "Kubernetes", "ElevenLabs"]);
+        assert_eq!(terms(&list, 10, 100), words(&["Tauri", "Deepgram", "Kubernetes", "ElevenLabs"]));
+        assert_eq!(terms(&list, 2, 100), words(&["Tauri", "Deepgram"]));
+        // "Tauri, Deepgram" is 15 characters; adding ", Kubernetes" would exceed 20
+        assert_eq!(terms(&list, 10, 20), words(&["Tauri", "Deepgram"]));
+    }
+
+    #[test]
+    fn test_from_settings_without_word_correction() {
+        let settings = Settings {
+            custom_words: words(&["Tauri", "Deepgram"]),
+            word_correction_enabled: false,
+            ..Settings::default()
+        };
+        assert_eq!(from_settings(&settings), words(&["Tauri", "Deepgram"]));
+
+        let settings = Settings { vocabulary_biasing: false, ..settings };
+        assert!(from_settings(&settings).is_empty());
+    }
+
+    #[test]
+    fn test_prompt() {
+        assert_eq!(prompt(&words(&["Tauri", "Deepgram"]), 100).as_deref(), Some("Glossary: Tauri, Deepgram."));
+        assert_eq!(prompt(&words(&["Tauri", "Deepgram"]), 20).as_deref(), Some("Glossary: Tauri."));
+        assert_eq!(prompt(&words(&["  "]), 100), None);
+    }
+
+    #[test]
+    fn test_whisper_prompt() {
+        assert_eq!(
+            whisper_prompt(&words(&["Tauri"]), Some(" We shipped it. ")).as_deref(),
+            Some("Glossary: Tauri. We shipped it.")
+        );
+        assert_eq!(whisper_prompt(&[], Some("We shipped it.")).as_deref(), Some("We shipped it."));
+        assert_eq!(whisper_prompt(&words(&["Tauri"]), Some("")).as_deref(), Some("Glossary: Tauri."));
+        assert_eq!(whisper_prompt(&[], None), None);
+
+        let long = "word ".repeat(200);
+        assert!(whisper_prompt(&words(&["Tauri"]), Some(&long)).unwrap().chars().count() <= WHISPER_PROMPT_CHARS);
+    }
+}
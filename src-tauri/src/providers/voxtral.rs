@@ -1,9 +1,14 @@
+use async_trait::async_trait;
 use base64::Engine;
 use futures_util::{SinkExt, StreamExt, stream::SplitSink, stream::SplitStream};
 use serde::{Deserialize, Serialize};
 use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream};
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 
+use super::streaming::{SinkMessage, StreamChannels, StreamConfig, StreamEvent, StreamSession, StreamingProvider};
+use super::ProviderResult;
+use crate::commands::settings::Settings;
+
 // ============================================================================
 // Voxtral Realtime Streaming Structures
 // ============================================================================
@@ -59,7 +64,7 @@ type WsStream = SplitStream<tokio_tungstenite::WebSocketStream<MaybeTlsStream<to
 async fn connect_voxtral_stream(
     api_key: &str,
     delay_ms: u32,
-) -> Result<(WsSink, WsStream), Box<dyn std::error::Error + Send + Sync>> {
+) -> ProviderResult<(WsSink, WsStream)> {
     let url = "wss://api.mistral.ai/v1/audio/transcriptions/realtime?model=voxtral-mini-transcribe-realtime-2602";
 
     let mut request = url.into_client_request()?;
@@ -102,10 +107,10 @@ async fn connect_voxtral_stream(
     Ok((write, read))
 }
 
-/// Spawn a receive task that forwards transcription deltas to a channel.
+/// Spawn a receive task that forwards transcription deltas as stream events.
 fn spawn_receive_task(
     mut read: WsStream,
-    tx: tokio::sync::mpsc::Sender<String>,
+    events_tx: tokio::sync::mpsc::Sender<StreamEvent>,
     stream_name: &'static str,
 ) {
     tokio::spawn(async move {
@@ -115,23 +120,29 @@ fn spawn_receive_task(
                     if let Ok(event) = serde_json::from_str::<VoxtralEvent>(&text) {
                         if event.event_type == "transcription.text.delta" {
                             if let Some(transcript_text) = event.text {
-                                if !transcript_text.is_empty() {
-                                    if let Err(_) = tx.send(transcript_text).await {
-                                        break;
-                                    }
+                                if !transcript_text.is_empty()
+                                    && events_tx.send(StreamEvent::Final(transcript_text)).await.is_err()
+                                {
+                                    return;
                                 }
                             }
                         } else if event.event_type == "error" {
                             eprintln!("[Voxtral/{}] Server error: {:?}", stream_name, event.text);
+                            let message = event.text.unwrap_or_else(|| "Unknown error".to_string());
+                            let _ = events_tx.send(StreamEvent::Error(message.into())).await;
                             break;
                         }
                     }
                 }
                 Ok(Message::Close(_)) => break,
-                Err(_) => break,
+                Err(e) => {
+                    let _ = events_tx.send(StreamEvent::Error(e.into())).await;
+                    break;
+                }
                 _ => {}
             }
         }
+        let _ = events_tx.send(StreamEvent::Closed).await;
     });
 }
 
@@ -139,52 +150,85 @@ fn spawn_receive_task(
 // Voxtral WebSocket Streaming (Public API)
 // ============================================================================
 
-/// Start Voxtral realtime streaming session.
+/// Voxtral realtime streaming transcription.
 ///
 /// Opens a connection to Voxtral's realtime STT endpoint configured for
-/// low latency (240ms) and returns channels to pipe audio in and transcript out.
-///
-/// Returns: (audio_tx, transcript_rx)
-pub async fn start_streaming(
-    api_key: String,
-) -> Result<(
-    tokio::sync::mpsc::Sender<Vec<u8>>,
-    tokio::sync::mpsc::Receiver<String>,
-), Box<dyn std::error::Error + Send + Sync>> {
+/// low latency (240ms). The language is auto-detected.
+pub struct VoxtralProvider;
 
-    // Open the fast stream
-    let (mut ws_write, ws_read) = connect_voxtral_stream(&api_key, 240).await?;
+#[async_trait]
+impl StreamingProvider for VoxtralProvider {
+    fn id(&self) -> &'static str {
+        "voxtral"
+    }
 
-    let (audio_tx, mut audio_rx) = tokio::sync::mpsc::channel::<Vec<u8>>(100);
-    let (transcript_tx, transcript_rx) = tokio::sync::mpsc::channel::<String>(100);
+    fn display_name(&self) -> &'static str {
+        "Voxtral"
+    }
 
-    spawn_receive_task(ws_read, transcript_tx, "fast");
+    fn api_key<'a>(&self, settings: &'a Settings) -> &'a str {
+        &settings.mistral_api_key
+    }
 
-    // Spawn audio send task
-    tokio::spawn(async move {
-        while let Some(audio_data) = audio_rx.recv().await {
-            if audio_data.is_empty() {
-                let end_msg = serde_json::to_string(&AudioEnd {
-                    msg_type: "input_audio.end".to_string(),
-                }).unwrap();
-                let _ = ws_write.send(Message::Text(end_msg)).await;
-
-                tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-                let _ = ws_write.send(Message::Close(None)).await;
-                break;
-            }
+    /// The frontend streams 16 kHz PCM16
+    fn encoding(&self) -> Option<&'static str> {
+        Some("linear16")
+    }
+
+    /// Voxtral always auto-detects the language
+    fn stream_language(&self, _language: &str) -> Option<String> {
+        None
+    }
+
+    /// Voxtral sends syllable and word fragments as text deltas
+    fn emits_word_deltas(&self) -> bool {
+        true
+    }
+
+    async fn connect(&self, config: StreamConfig) -> ProviderResult<StreamSession> {
+        // Open the fast stream
+        let (mut ws_write, ws_read) = connect_voxtral_stream(&config.api_key, 240).await?;
 
-            let base64_audio = base64::engine::general_purpose::STANDARD.encode(&audio_data);
-            let audio_msg = serde_json::to_string(&AudioAppend {
-                msg_type: "input_audio.append".to_string(),
-                audio: base64_audio,
-            }).unwrap();
+        let (session, channels) = StreamSession::channel();
+        let StreamChannels { mut audio_rx, events_tx } = channels;
 
-            if let Err(_) = ws_write.send(Message::Text(audio_msg)).await {
-                break;
+        spawn_receive_task(ws_read, events_tx.clone(), "fast");
+
+        // Spawn audio send task
+        tokio::spawn(async move {
+            while let Some(message) = audio_rx.recv().await {
+                match message {
+                    SinkMessage::Audio(audio_data) => {
+                        let base64_audio = base64::engine::general_purpose::STANDARD.encode(&audio_data);
+                        let audio_msg = serde_json::to_string(&AudioAppend {
+                            msg_type: "input_audio.append".to_string(),
+                            audio: base64_audio,
+                        }).unwrap();
+
+                        if let Err(e) = ws_write.send(Message::Text(audio_msg)).await {
+                            let _ = events_tx.send(StreamEvent::Error(e.into())).await;
+                            break;
+                        }
+                    }
+                    SinkMessage::KeepAlive => {
+                        if ws_write.send(Message::Ping(Vec::new())).await.is_err() {
+                            break;
+                        }
+                    }
+                    SinkMessage::Close => {
+                        let end_msg = serde_json::to_string(&AudioEnd {
+                            msg_type: "input_audio.end".to_string(),
+                        }).unwrap();
+                        let _ = ws_write.send(Message::Text(end_msg)).await;
+
+                        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+                        let _ = ws_write.send(Message::Close(None)).await;
+                        break;
+                    }
+                }
             }
-        }
-    });
+        });
 
-    Ok((audio_tx, transcript_rx))
+        Ok(session)
+    }
 }
@@ -0,0 +1,190 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::mpsc::error::TryRecvError;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use super::streaming::{self as stream, StreamConfig, StreamEvent, StreamSession, StreamingProvider};
+use super::vocabulary;
+use crate::commands::settings::Settings;
+
+/// Deepgram closes sockets after 10 seconds without audio or KeepAlive
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A warm socket nobody used for this long is closed rather than kept open indefinitely
+const MAX_IDLE: Duration = Duration::from_secs(5 * 60);
+
+/// Provider and connection parameters the next streaming session will request
+/// (see `StreamConfig::for_provider`). None when pre-warming is off, the selected
+/// provider is not a streaming one, or it has no API key.
+pub fn warm_target(settings: &Settings) -> Option<(Arc<dyn StreamingProvider>, StreamConfig)> {
+    if !settings.streaming_prewarm {
+        return None;
+    }
+    let provider = stream::provider(&settings.api_service)?;
+    let api_key = provider.api_key(settings).trim().to_string();
+    if api_key.is_empty() {
+        return None;
+    }
+
+    let config = StreamConfig::for_provider(
+        provider.as_ref(),
+        api_key,
+        &settings.transcription_language,
+        settings.text_formatted,
+        None,
+        vocabulary::from_settings(settings),
+    );
+    Some((provider, config))
+}
+
+/// An open, idle provider session waiting for the next dictation
+struct WarmConnection {
+    provider_id: &'static str,
+    config: StreamConfig,
+    session: StreamSession,
+    keepalive: JoinHandle<()>,
+}
+
+impl WarmConnection {
+    fn matches(&self, provider_id: &str, config: &StreamConfig) -> bool {
+        self.provider_id == provider_id && &self.config == config
+    }
+
+    async fn discard(self) {
+        self.keepalive.abort();
+        self.session.sink.close().await;
+    }
+}
+
+/// Pool of at most one pre-opened streaming connection, so pressing the hotkey
+/// skips the WebSocket handshake
+#[derive(Default)]
+pub struct WarmPool {
+    slot: Mutex<Option<WarmConnection>>,
+}
+
+impl WarmPool {
+    /// Take the warm connection if it was opened for this provider and config and is still open
+    pub async fn take(&self, provider_id: &str, config: &StreamConfig) -> Option<StreamSession> {
+        let warm = self.slot.lock().await.take()?;
+        if !warm.matches(provider_id, config) {
+            warm.discard().await;
+            return None;
+        }
+
+        warm.keepalive.abort();
+        let mut session = warm.session;
+        // Nothing is transcribed while idle; an error or close means the socket is gone
+        loop {
+            match session.events.try_recv() {
+                Ok(StreamEvent::Error(_)) | Ok(StreamEvent::Closed) | Err(TryRecvError::Disconnected) => {
+                    session.sink.close().await;
+                    return None;
+                }
+                Ok(_) => {}
+                Err(TryRecvError::Empty) => return Some(session),
+            }
+        }
+    }
+
+    /// Make sure a connection for this provider and config is open, replacing one for another target
+    pub async fn fill(&self, provider: Arc<dyn StreamingProvider>, config: StreamConfig) {
+        {
+            let slot = self.slot.lock().await;
+            if let Some(warm) = slot.as_ref() {
+                if warm.matches(provider.id(), &config) && !warm.keepalive.is_finished() {
+                    return;
+                }
+            }
+        }
+        // Settings changed or the key was rotated: drop the old socket before dialing
+        self.clear().await;
+
+        let session = match provider.connect(config.clone()).await {
+            Ok(session) => session,
+            Err(e) => {
+                eprintln!("[Streaming] Failed to pre-warm {}: {}", provider.display_name(), e);
+                return;
+            }
+        };
+
+        let sink = session.sink.clone();
+        let keepalive = tokio::spawn(async move {
+            let expires = Instant::now() + MAX_IDLE;
+            while Instant::now() + KEEPALIVE_INTERVAL < expires {
+                tokio::time::sleep(KEEPALIVE_INTERVAL).await;
+                if sink.keep_alive().await.is_err() {
+                    return;
+                }
+            }
+            sink.close().await;
+        });
+
+        let warm = WarmConnection {
+            provider_id: provider.id(),
+            config,
+            session,
+            keepalive,
+        };
+        // A concurrent fill may have won the race; keep the newest connection
+        if let Some(previous) = self.slot.lock().await.replace(warm) {
+            previous.discard().await;
+        }
+    }
+
+    /// Close the warm connection, if any
+    pub async fn clear(&self) {
+        let warm = self.slot.lock().await.take();
+        if let Some(warm) = warm {
+            warm.discard().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Language and encoding each frontend provider passes to `start_streaming_transcription`
+    fn frontend_request(provider_id: &str, language: &str) -> (String, Option<String>) {
+        let language = match (provider_id, language) {
+            ("voxtral", _) | (_, "multilingual") => "multi".to_string(),
+            (_, code) => code.to_string(),
+        };
+        let encoding = matches!(provider_id, "deepgram" | "voxtral").then(|| "linear16".to_string());
+        (language, encoding)
+    }
+
+    #[test]
+    fn test_warm_config_matches_session_start() {
+        for provider_id in ["deepgram", "cartesia", "elevenlabs", "voxtral"] {
+            for language in ["multilingual", "it"] {
+                let settings = Settings {
+                    streaming_prewarm: true,
+                    api_service: provider_id.to_string(),
+                    transcription_language: language.to_string(),
+                    deepgram_api_key: "key".to_string(),
+                    cartesia_api_key: "key".to_string(),
+                    elevenlabs_api_key: "key".to_string(),
+                    mistral_api_key: "key".to_string(),
+                    ..Settings::default()
+                };
+
+                let (provider, warm_config) = warm_target(&settings).unwrap();
+                let (start_language, encoding) = frontend_request(provider_id, language);
+                let start_config = StreamConfig::for_provider(
+                    provider.as_ref(),
+                    "key".to_string(),
+                    &start_language,
+                    settings.text_formatted,
+                    encoding,
+                    vocabulary::from_settings(&settings),
+                );
+                assert_eq!(warm_config, start_config, "{} / {}", provider_id, language);
+            }
+        }
+    }
+}
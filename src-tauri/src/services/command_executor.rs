@@ -0,0 +1,96 @@
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::services;
+use crate::voice_commands::{CommandAction, Item};
+
+/// Insert text with the configured insertion mode ("typing" or clipboard paste)
+pub fn insert_text(text: &str, insertion_mode: &str, app: &AppHandle) -> Result<(), String> {
+    if insertion_mode == "typing" {
+        services::direct_typing::inject_text_native(text, app).map_err(|e| e.to_string())
+    } else {
+        services::clipboard_paste::insert_text_via_clipboard(text, app)
+    }
+}
+
+/// Execute interpreted items in spoken order: text is inserted, actions are run.
+/// A failed action is logged and skipped; a failed insertion stops execution.
+pub async fn execute_items(items: &[Item], insertion_mode: &str, app: &AppHandle) -> Result<(), String> {
+    for item in items {
+        match item {
+            Item::Text(text) => {
+                if !text.is_empty() {
+                    insert_text(text, insertion_mode, app)
+                        .map_err(|e| format!("Failed to insert text: {}", e))?;
+                }
+            }
+            Item::Action(action) => {
+                if let Err(e) = execute_action(action, insertion_mode, app).await {
+                    eprintln!("[Voice Commands] Failed to execute action: {}", e);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Execute a voice command action
+pub async fn execute_action(action: &CommandAction, insertion_mode: &str, app: &AppHandle) -> Result<(), String> {
+    match action {
+        CommandAction::KeyPress(key) => {
+            services::direct_typing::send_key_native(key, app)
+                .map_err(|e| e.to_string())
+        }
+        CommandAction::KeyCombo(modifier, key) => {
+            services::direct_typing::send_key_combo_native(modifier, key, app)
+                .map_err(|e| e.to_string())
+        }
+        CommandAction::DeleteLastWord => {
+            // Smart delete: if text is selected, delete the selection.
+            // If nothing is selected, fall back to Ctrl+Backspace to delete last word.
+            let app_clone = app.clone();
+            tokio::task::spawn_blocking(move || {
+                services::clipboard_paste::delete_selected_or_last_word(&app_clone)
+            })
+            .await
+            .map_err(|e| format!("Task join error: {}", e))?
+        }
+        CommandAction::Rewrite => {
+            // Emit event to trigger text rewrite - frontend handles smart selection
+            if let Some(window) = app.get_webview_window("main") {
+                tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+                let _ = window.emit("sparkle-trigger", ());
+            } else {
+                eprintln!("[Voice Commands] Main window not found for rewrite trigger");
+            }
+            Ok(())
+        }
+        CommandAction::PauseDictation => {
+            // Emit event to pause/stop dictation
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.emit("toggle-recording", ());
+            }
+            Ok(())
+        }
+        CommandAction::Wait(ms) => {
+            tokio::time::sleep(tokio::time::Duration::from_millis(*ms)).await;
+            Ok(())
+        }
+        CommandAction::Macro(steps) => execute_macro(steps, insertion_mode, app).await,
+        CommandAction::InsertText(_) => {
+            // Spoken punctuation is merged into text items by the interpreter
+            Ok(())
+        }
+    }
+}
+
+/// Run macro steps in order (placeholders were filled by the interpreter).
+/// The first failed step stops the macro.
+async fn execute_macro(steps: &[CommandAction], insertion_mode: &str, app: &AppHandle) -> Result<(), String> {
+    for step in steps {
+        match step {
+            CommandAction::InsertText(text) => insert_text(text, insertion_mode, app)?,
+            step => Box::pin(execute_action(step, insertion_mode, app)).await?,
+        }
+    }
+    Ok(())
+}
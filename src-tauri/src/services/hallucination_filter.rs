@@ -0,0 +1,423 @@
+use crate::commands::settings::HallucinationFilterSettings;
+use crate::providers::transcript::{Segment, Transcript};
+
+/// Longest phrase, in words, checked for back-to-back repetition
+const MAX_REPEATED_PHRASE_WORDS: usize = 8;
+
+/// Subtitle credits Whisper-family models produce from silence or noise, taken from
+/// their training data. Matched against the start of a sentence.
+const CREDIT_PHRASES: &[(&str, &[&str])] = &[
+    ("en", &["subtitles by the amara org community"]),
+    ("de", &[
+        "untertitel im auftrag des zdf",
+        "untertitel der amara org community",
+        "untertitelung",
+        "copyright wdr",
+    ]),
+    ("fr", &["sous titres réalisés par", "sous titrage"]),
+    ("es", &["subtítulos realizados por", "subtítulos por la comunidad de amara org"]),
+    ("it", &[
+        "sottotitoli creati dalla comunità amara org",
+        "sottotitoli a cura di",
+        "sottotitoli e revisione a cura di",
+    ]),
+    ("pt", &["legendas pela comunidade amara org"]),
+    ("nl", &["ondertitels ingediend door de amara org gemeenschap"]),
+    ("ru", &["субтитры сделал", "субтитры создавал", "субтитры подогнал", "редактор субтитров"]),
+    ("zh", &["请不吝点赞 订阅 转发 打赏支持明镜与点点栏目"]),
+];
+
+/// Video outros and credit openers that real dictation also uses ("Thanks for watching
+/// the kids", "Translated by the legal team, …"). A sentence is dropped when it is
+/// exactly the phrase, or starts with it and the provider reports weak speech evidence.
+const GENERIC_CREDIT_PHRASES: &[(&str, &[&str])] = &[
+    ("en", &[
+        "thank you for watching",
+        "thanks for watching",
+        "thank you so much for watching",
+        "don't forget to like and subscribe",
+        "see you in the next video",
+        "please subscribe",
+        "like and subscribe",
+        "subtitles by",
+        "subtitles made by",
+        "captions by",
+        "transcribed by",
+        "transcription by",
+        "translated by",
+    ]),
+    ("de", &["vielen dank fürs zuschauen", "danke fürs zuschauen", "untertitel von"]),
+    ("fr", &["merci d'avoir regardé", "sous titres par", "abonnez vous"]),
+    ("es", &["gracias por ver el video", "gracias por ver", "subtitulado por", "suscríbete"]),
+    ("it", &["grazie per la visione", "iscriviti al canale"]),
+    ("pt", &["obrigado por assistir", "legendas por", "inscreva se no canal"]),
+    ("nl", &["bedankt voor het kijken", "ondertiteld door", "ondertiteling door"]),
+    ("ru", &["спасибо за просмотр", "продолжение следует"]),
+    ("ja", &["ご視聴ありがとうございました", "チャンネル登録", "字幕は"]),
+    ("zh", &["感谢观看", "谢谢观看", "字幕由", "请订阅"]),
+];
+
+/// Short utterances Whisper emits for breathing or keyboard noise. Real dictations say
+/// these too, so they are only dropped when they are the whole transcript and the
+/// provider reports weak speech evidence.
+const SHORT_PHRASES: &[(&str, &[&str])] = &[
+    ("en", &["you", "thank you", "thank you very much", "thank you so much", "thanks", "bye", "bye bye", "so", "okay"]),
+    ("de", &["danke", "vielen dank", "tschüss"]),
+    ("fr", &["merci", "merci beaucoup", "au revoir"]),
+    ("es", &["gracias", "muchas gracias", "adiós"]),
+    ("it", &["grazie", "grazie mille", "ciao"]),
+    ("pt", &["obrigado", "obrigada", "tchau"]),
+    ("nl", &["dank je", "dank u", "bedankt", "doei"]),
+    ("ru", &["спасибо", "пока"]),
+    ("ja", &["ありがとうございました", "ありがとうございます"]),
+    ("zh", &["谢谢", "谢谢大家"]),
+];
+
+/// Remove hallucinated output from a batch transcript and return the text to keep:
+/// segments the provider marks as silence, a lone filler phrase on weak evidence,
+/// subtitle credits and back-to-back repetition loops.
+/// `language` is the requested language code; None checks the phrases of every language.
+pub fn filter_transcript(
+    transcript: &Transcript,
+    language: Option<&str>,
+    settings: &HallucinationFilterSettings,
+) -> String {
+    if !settings.enabled {
+        return transcript.text.clone();
+    }
+
+    let text = drop_silent_segments(transcript, settings);
+    let weak = weak_speech(transcript, settings);
+    if weak && is_short_phrase(&text, language) {
+        return String::new();
+    }
+
+    let text = remove_credit_sentences(&text, language, &settings.extra_phrases, weak);
+    collapse_repetitions(&text, settings.max_repeats)
+}
+
+/// Whisper's own rule for skipping a window: likely no speech and low confidence.
+/// Providers that omit `avg_logprob` are judged on `no_speech_prob` alone.
+fn is_silent_segment(segment: &Segment, settings: &HallucinationFilterSettings) -> bool {
+    segment
+        .no_speech_prob
+        .is_some_and(|prob| prob > settings.no_speech_threshold)
+        && segment
+            .avg_logprob
+            .is_none_or(|logprob| logprob < settings.logprob_threshold)
+}
+
+/// Transcript text without silent segments (unchanged when none are dropped)
+fn drop_silent_segments(transcript: &Transcript, settings: &HallucinationFilterSettings) -> String {
+    let kept: Vec<&Segment> = transcript
+        .segments
+        .iter()
+        .filter(|segment| !is_silent_segment(segment, settings))
+        .collect();
+    if kept.len() == transcript.segments.len() {
+        return transcript.text.clone();
+    }
+
+    kept.iter()
+        .map(|segment| segment.text.trim())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// True when a segment is half-way to the silence thresholds
+fn weak_speech(transcript: &Transcript, settings: &HallucinationFilterSettings) -> bool {
+    transcript.segments.iter().any(|segment| {
+        segment
+            .no_speech_prob
+            .is_some_and(|prob| prob > settings.no_speech_threshold / 2.0)
+            || segment
+                .avg_logprob
+                .is_some_and(|logprob| logprob < settings.logprob_threshold)
+    })
+}
+
+fn is_short_phrase(text: &str, language: Option<&str>) -> bool {
+    let normalized = normalize(text);
+    phrases_for(SHORT_PHRASES, language).any(|phrase| normalized == phrase)
+}
+
+/// Phrases for `language` plus English (Whisper often falls back to English credits),
+/// or every language when auto-detecting
+fn phrases_for<'a>(
+    table: &'a [(&'a str, &'a [&'a str])],
+    language: Option<&'a str>,
+) -> impl Iterator<Item = &'a str> + 'a {
+    let language = language.map(|code| code.split(['-', '_']).next().unwrap_or(code).to_lowercase());
+    table
+        .iter()
+        .filter(move |(code, _)| match &language {
+            Some(language) => *code == "en" || *code == language.as_str(),
+            None => true,
+        })
+        .flat_map(|(_, phrases)| phrases.iter().copied())
+}
+
+/// Lowercase, turn punctuation into spaces and collapse whitespace
+fn normalize(text: &str) -> String {
+    let cleaned: String = text
+        .chars()
+        .map(|c| match c {
+            '’' => '\'',
+            c if c.is_alphanumeric() || c == '\'' => c,
+            _ => ' ',
+        })
+        .collect();
+    cleaned.to_lowercase().split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Split after sentence punctuation that ends a sentence: ASCII terminators followed by
+/// whitespace (so "amara.org" stays whole) and CJK terminators anywhere
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        let next = chars.peek().map(|&(_, next)| next);
+        let ends = match c {
+            '。' | '！' | '？' => true,
+            '.' | '!' | '?' | '…' => next.is_none_or(char::is_whitespace),
+            _ => false,
+        };
+        if ends {
+            // Keep the whitespace after the terminator with this sentence
+            let mut end = index + c.len_utf8();
+            while let Some(&(next_index, next)) = chars.peek() {
+                if !next.is_whitespace() {
+                    break;
+                }
+                end = next_index + next.len_utf8();
+                chars.next();
+            }
+            sentences.push(&text[start..end]);
+            start = end;
+        }
+    }
+    if start < text.len() {
+        sentences.push(&text[start..]);
+    }
+    sentences
+}
+
+fn normalize_phrases<'a>(phrases: impl Iterator<Item = &'a str>) -> Vec<String> {
+    phrases.map(normalize).filter(|phrase| !phrase.is_empty()).collect()
+}
+
+fn starts_with_phrase(normalized: &str, phrase: &str) -> bool {
+    normalized == phrase
+        || normalized.starts_with(&format!("{} ", phrase))
+        // Scripts written without spaces have no word boundary to check
+        || (!phrase.is_ascii() && normalized.starts_with(phrase))
+}
+
+fn is_credit_sentence(sentence: &str, phrases: &[String], generic: &[String], weak: bool) -> bool {
+    let normalized = normalize(sentence);
+    !normalized.is_empty()
+        && (phrases.iter().any(|phrase| starts_with_phrase(&normalized, phrase))
+            || generic.iter().any(|phrase| {
+                normalized == *phrase || (weak && starts_with_phrase(&normalized, phrase))
+            }))
+}
+
+/// Remove sentences that start with a known credit phrase. Outros and other generic
+/// phrases only remove a whole matching sentence unless `weak` speech evidence backs them.
+fn remove_credit_sentences(text: &str, language: Option<&str>, extra_phrases: &[String], weak: bool) -> String {
+    let phrases = normalize_phrases(
+        phrases_for(CREDIT_PHRASES, language).chain(extra_phrases.iter().map(String::as_str)),
+    );
+    let generic = normalize_phrases(phrases_for(GENERIC_CREDIT_PHRASES, language));
+
+    let sentences = split_sentences(text);
+    let kept: Vec<&str> = sentences
+        .iter()
+        .copied()
+        .filter(|sentence| !is_credit_sentence(sentence, &phrases, &generic, weak))
+        .collect();
+    if kept.len() == sentences.len() {
+        return text.to_string();
+    }
+    kept.concat().trim().to_string()
+}
+
+/// Collapse a word or phrase repeated back to back more than `max_repeats` times
+/// into a single occurrence. 0 disables the check.
+fn collapse_repetitions(text: &str, max_repeats: usize) -> String {
+    if max_repeats == 0 {
+        return text.to_string();
+    }
+
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let keys: Vec<String> = words.iter().map(|word| normalize(word)).collect();
+    let mut kept: Vec<&str> = Vec::with_capacity(words.len());
+    let mut collapsed = false;
+
+    let mut i = 0;
+    while i < words.len() {
+        let mut skip = None;
+        for size in 1..=MAX_REPEATED_PHRASE_WORDS {
+            if i + size * (max_repeats + 1) > words.len() {
+                break;
+            }
+            let phrase = &keys[i..i + size];
+            let mut count = 1;
+            while i + (count + 1) * size <= words.len() && keys[i + count * size..i + (count + 1) * size] == *phrase {
+                count += 1;
+            }
+            if count > max_repeats {
+                skip = Some((size, count));
+                break;
+            }
+        }
+
+        match skip {
+            Some((size, count)) => {
+                // Keep the last copy so trailing punctuation survives
+                let last = i + (count - 1) * size;
+                kept.extend_from_slice(&words[last..last + size]);
+                i += count * size;
+                collapsed = true;
+            }
+            None => {
+                kept.push(words[i]);
+                i += 1;
+            }
+        }
+    }
+
+    if collapsed {
+        kept.join(" ")
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> HallucinationFilterSettings {
+        HallucinationFilterSettings::default()
+    }
+
+    fn segment(text: &str, no_speech_prob: f64, avg_logprob: f64) -> Segment {
+        Segment {
+            text: text.to_string(),
+            start: 0.0,
+            end: 1.0,
+            avg_logprob: Some(avg_logprob),
+            no_speech_prob: Some(no_speech_prob),
+            compression_ratio: None,
+        }
+    }
+
+    fn with_segments(segments: Vec<Segment>) -> Transcript {
+        Transcript {
+            text: segments.iter().map(|s| s.text.as_str()).collect(),
+            segments,
+            ..Default::default()
+        }
+    }
+
+    fn filter(text: &str, language: Option<&str>) -> String {
+        filter_transcript(&Transcript::from_text(text), language, &settings())
+    }
+
+    #[test]
+    fn test_removes_subtitle_credits() {
+        assert_eq!(filter(" Thank you for watching!", Some("en")), "");
+        assert_eq!(
+            filter("Let's meet at noon. Subtitles by the Amara.org community", Some("en")),
+            "Let's meet at noon."
+        );
+        assert_eq!(filter("Untertitel im Auftrag des ZDF, 2021", Some("de")), "");
+        assert_eq!(filter("Sous-titres réalisés par la communauté d'Amara.org", Some("fr")), "");
+        assert_eq!(filter("ご視聴ありがとうございました", None), "");
+        assert_eq!(filter("Субтитры сделал DimaTorzok", Some("ru")), "");
+        // Other languages' credits only apply when auto-detecting
+        assert_eq!(filter("Sottotitoli a cura di QTSS", Some("de")), "Sottotitoli a cura di QTSS");
+        assert_eq!(filter("Sottotitoli a cura di QTSS", None), "");
+    }
+
+    #[test]
+    fn test_keeps_normal_dictation() {
+        let text = "Thanks for the update. I'll send the subtitles by Friday.";
+        assert_eq!(filter(text, Some("en")), text);
+        // A lone "Thank you." is kept without segment statistics to judge it by
+        assert_eq!(filter("Thank you.", Some("en")), "Thank you.");
+        assert_eq!(filter("no no no", Some("en")), "no no no");
+    }
+
+    #[test]
+    fn test_keeps_dictation_starting_with_generic_phrases() {
+        let text = "Translated by the legal team, the contract says we pay in March.";
+        assert_eq!(filter(text, Some("en")), text);
+        assert_eq!(filter("Please subscribe me to the newsletter.", Some("en")), "Please subscribe me to the newsletter.");
+        let text = "Captions by default are off. Transcribed by hand, it took a week.";
+        assert_eq!(filter(text, Some("en")), text);
+        assert_eq!(filter("Subtitulado por Juan en la reunión.", Some("es")), "Subtitulado por Juan en la reunión.");
+
+        // The bare phrase, or the phrase on weak speech evidence, is still removed
+        assert_eq!(filter("See you then. Please subscribe!", Some("en")), "See you then.");
+        let weak = with_segments(vec![segment(" Translated by Jane Doe.", 0.45, -0.6)]);
+        assert_eq!(filter_transcript(&weak, Some("en"), &settings()), "");
+    }
+
+    #[test]
+    fn test_keeps_dictation_starting_with_outros() {
+        let text = "Thanks for watching the kids yesterday.";
+        assert_eq!(filter(text, Some("en")), text);
+        assert_eq!(filter("Gracias por ver mi propuesta.", Some("es")), "Gracias por ver mi propuesta.");
+        assert_eq!(filter("Продолжение следует в понедельник.", Some("ru")), "Продолжение следует в понедельник.");
+
+        assert_eq!(filter("Let's meet at noon. Thanks for watching!", Some("en")), "Let's meet at noon.");
+        let weak = with_segments(vec![segment(" Thanks for watching and see you soon.", 0.45, -0.6)]);
+        assert_eq!(filter_transcript(&weak, Some("en"), &settings()), "");
+    }
+
+    #[test]
+    fn test_collapses_repetition_loops() {
+        assert_eq!(
+            filter("I think that I think that I think that I think that I think that we should go", Some("en")),
+            "I think that we should go"
+        );
+        assert_eq!(filter("Thank you. Thank you. Thank you. Thank you. Thank you.", None), "Thank you.");
+        assert_eq!(filter("the the the the the end", Some("en")), "the end");
+        assert_eq!(collapse_repetitions("a a a a", 0), "a a a a");
+    }
+
+    #[test]
+    fn test_uses_segment_statistics() {
+        let transcript = with_segments(vec![
+            segment(" Please review the draft.", 0.02, -0.25),
+            segment(" Thank you.", 0.91, -1.3),
+        ]);
+        assert_eq!(filter_transcript(&transcript, Some("en"), &settings()), "Please review the draft.");
+
+        // Confident speech is kept even when no_speech_prob is high
+        let transcript = with_segments(vec![segment(" Ship it.", 0.7, -0.2)]);
+        assert_eq!(filter_transcript(&transcript, Some("en"), &settings()), " Ship it.");
+
+        // A lone filler phrase on weak evidence is dropped, on strong evidence kept
+        let weak = with_segments(vec![segment(" Thank you.", 0.45, -0.6)]);
+        assert_eq!(filter_transcript(&weak, Some("en"), &settings()), "");
+        let strong = with_segments(vec![segment(" Thank you.", 0.03, -0.2)]);
+        assert_eq!(filter_transcript(&strong, Some("en"), &settings()), " Thank you.");
+    }
+
+    #[test]
+    fn test_settings() {
+        let mut custom = settings();
+        custom.extra_phrases = vec!["Produced by".to_string()];
+        let transcript = Transcript::from_text("See you then. Produced by ACME Studios.");
+        assert_eq!(filter_transcript(&transcript, Some("en"), &custom), "See you then.");
+
+        custom.enabled = false;
+        let transcript = Transcript::from_text("Thanks for watching!");
+        assert_eq!(filter_transcript(&transcript, Some("en"), &custom), "Thanks for watching!");
+    }
+}
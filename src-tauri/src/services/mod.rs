@@ -1,6 +1,13 @@
 // Service modules
 pub mod clipboard_paste;
+pub mod command_executor;
 pub mod direct_typing;
+pub mod hallucination_filter;
+pub mod offline_queue;
+pub mod segment_context;
+pub mod segment_sequencer;
+pub mod transcript_history;
+pub mod transcript_pipeline;
 pub mod windows_focus;
 pub mod word_correction;
 
@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::sync::Notify;
+
+use crate::providers::ProviderError;
+
+/// First wait before retrying after the network failed again; doubles up to `MAX_RETRY_DELAY`
+const MIN_RETRY_DELAY: Duration = Duration::from_secs(15);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+/// Failures other than network loss after which a queued segment is dropped
+pub const MAX_ATTEMPTS: u32 = 5;
+
+/// Metadata saved next to a queued segment's audio (`<id>.json` and `<id>.wav`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedSegment {
+    pub id: String,
+    /// When the audio was recorded, in milliseconds since the Unix epoch
+    pub recorded_at: i64,
+    /// Transcription provider selected at recording time
+    pub provider: String,
+    pub language: Option<String>,
+    pub text_formatted: bool,
+    /// Spoken punctuation is applied to the recovered text (key commands are not run)
+    #[serde(default = "default_voice_commands")]
+    pub voice_commands: bool,
+    /// Failed retries that were not caused by network loss
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+/// Batch segments that could not be transcribed because the network was down,
+/// stored in `<app data>/offline_queue` until they can be retried
+#[derive(Default)]
+pub struct OfflineQueue {
+    /// Wakes the recovery loop (segment queued, or a live request got through)
+    wake: Notify,
+    counter: AtomicU64,
+}
+
+impl OfflineQueue {
+    /// Save a segment's WAV audio and metadata
+    pub fn enqueue(
+        &self,
+        app: &AppHandle,
+        provider: &str,
+        language: Option<String>,
+        text_formatted: bool,
+        voice_commands: bool,
+        audio: &[u8],
+    ) -> Result<QueuedSegment, String> {
+        let recorded_at = chrono::Utc::now().timestamp_millis();
+        let segment = QueuedSegment {
+            // Sortable ids keep recovery in recording order
+            id: format!("{:013}_{:04}", recorded_at, self.counter.fetch_add(1, Ordering::Relaxed) % 10_000),
+            recorded_at,
+            provider: provider.to_string(),
+            language,
+            text_formatted,
+            voice_commands,
+            attempts: 0,
+        };
+        write_segment(&queue_dir(app)?, &segment, audio)?;
+        self.wake.notify_one();
+        Ok(segment)
+    }
+
+    /// Queued segments, oldest first
+    pub fn pending(&self, app: &AppHandle) -> Vec<QueuedSegment> {
+        queue_dir(app).map(|dir| read_segments(&dir)).unwrap_or_default()
+    }
+
+    pub fn audio(&self, app: &AppHandle, segment: &QueuedSegment) -> Result<Vec<u8>, String> {
+        fs::read(queue_dir(app)?.join(format!("{}.wav", segment.id))).map_err(|e| e.to_string())
+    }
+
+    /// Store updated metadata (attempt count)
+    pub fn update(&self, app: &AppHandle, segment: &QueuedSegment) -> Result<(), String> {
+        write_metadata(&queue_dir(app)?, segment)
+    }
+
+    pub fn remove(&self, app: &AppHandle, segment: &QueuedSegment) {
+        if let Ok(dir) = queue_dir(app) {
+            remove_segment(&dir, &segment.id);
+        }
+    }
+
+    /// Retry now: the network is evidently back
+    pub fn wake(&self) {
+        self.wake.notify_one();
+    }
+
+    /// Sleep for `delay` or until woken
+    pub async fn wait(&self, delay: Duration) {
+        let _ = tokio::time::timeout(delay, self.wake.notified()).await;
+    }
+}
+
+fn default_voice_commands() -> bool {
+    true
+}
+
+/// Failures that mean the provider could not be reached, so the audio is kept
+pub fn is_offline_error(error: &ProviderError) -> bool {
+    matches!(error, ProviderError::Network(_) | ProviderError::Timeout)
+}
+
+/// Wait before the next retry after `failures` consecutive offline passes
+pub fn retry_delay(failures: u32) -> Duration {
+    if failures == 0 {
+        return MAX_RETRY_DELAY;
+    }
+    MIN_RETRY_DELAY
+        .saturating_mul(1 << (failures - 1).min(16))
+        .min(MAX_RETRY_DELAY)
+}
+
+fn queue_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join("offline_queue");
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+fn write_segment(dir: &Path, segment: &QueuedSegment, audio: &[u8]) -> Result<(), String> {
+    // Audio first, so metadata never points at a missing file
+    fs::write(dir.join(format!("{}.wav", segment.id)), audio).map_err(|e| e.to_string())?;
+    write_metadata(dir, segment)
+}
+
+fn write_metadata(dir: &Path, segment: &QueuedSegment) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(segment).map_err(|e| e.to_string())?;
+    fs::write(dir.join(format!("{}.json", segment.id)), json).map_err(|e| e.to_string())
+}
+
+fn read_segments(dir: &Path) -> Vec<QueuedSegment> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut segments: Vec<QueuedSegment> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter_map(|content| serde_json::from_str(&content).ok())
+        .collect();
+    segments.sort_by(|a, b| a.id.cmp(&b.id));
+    segments
+}
+
+fn remove_segment(dir: &Path, id: &str) {
+    let _ = fs::remove_file(dir.join(format!("{}.json", id)));
+    let _ = fs::remove_file(dir.join(format!("{}.wav", id)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(id: &str) -> QueuedSegment {
+        QueuedSegment {
+            id: id.to_string(),
+            recorded_at: 0,
+            provider: "groq".to_string(),
+            language: Some("en".to_string()),
+            text_formatted: true,
+            voice_commands: true,
+            attempts: 0,
+        }
+    }
+
+    #[test]
+    fn test_queue_files_round_trip_in_order() {
+        let dir = std::env::temp_dir().join(format!("dictate_offline_queue_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        write_segment(&dir, &segment("0000000000002_0001"), b"second").unwrap();
+        write_segment(&dir, &segment("0000000000001_0000"), b"first").unwrap();
+        let ids: Vec<String> = read_segments(&dir).into_iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec!["0000000000001_0000", "0000000000002_0001"]);
+        assert_eq!(fs::read(dir.join("0000000000001_0000.wav")).unwrap(), b"first");
+
+        remove_segment(&dir, "0000000000001_0000");
+        assert_eq!(read_segments(&dir).len(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off() {
+        assert_eq!(retry_delay(1), Duration::from_secs(15));
+        assert_eq!(retry_delay(2), Duration::from_secs(30));
+        assert_eq!(retry_delay(10), MAX_RETRY_DELAY);
+        assert_eq!(retry_delay(100), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn test_offline_errors() {
+        assert!(is_offline_error(&ProviderError::Network("dns".to_string())));
+        assert!(is_offline_error(&ProviderError::Timeout));
+        assert!(!is_offline_error(&ProviderError::Auth(String::new())));
+    }
+}
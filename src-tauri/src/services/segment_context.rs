@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Rolling tail of the text already transcribed in each batch recording session.
+/// Sent as context with the next segment so casing, punctuation style and names
+/// stay consistent across VAD segments. Keyed by the VAD session id.
+#[derive(Default)]
+pub struct SegmentContexts {
+    tails: Mutex<HashMap<String, String>>,
+}
+
+impl SegmentContexts {
+    /// Context for the next segment of `session_id`, if anything was committed yet
+    pub fn get(&self, session_id: &str) -> Option<String> {
+        self.tails.lock().unwrap().get(session_id).cloned()
+    }
+
+    /// Append committed text, keeping at most `max_chars` characters
+    pub fn commit(&self, session_id: &str, text: &str, max_chars: usize) {
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        let mut tails = self.tails.lock().unwrap();
+        let combined = match tails.get(session_id) {
+            Some(previous) => format!("{} {}", previous, text),
+            None => text.to_string(),
+        };
+        tails.insert(session_id.to_string(), tail(&combined, max_chars).to_string());
+    }
+
+    /// Forget a finished session
+    pub fn clear(&self, session_id: &str) {
+        self.tails.lock().unwrap().remove(session_id);
+    }
+}
+
+/// The end of `text` within `max_chars` characters, starting at a word boundary
+/// (a single word longer than the budget is cut mid-word)
+pub fn tail(text: &str, max_chars: usize) -> &str {
+    let text = text.trim();
+    let count = text.chars().count();
+    if count <= max_chars {
+        return text;
+    }
+
+    let start = text
+        .char_indices()
+        .nth(count - max_chars)
+        .map_or(text.len(), |(index, _)| index);
+    let cut = &text[start..];
+    // Starting right after a space means no word was split
+    if text[..start].ends_with(char::is_whitespace) {
+        return cut.trim_start();
+    }
+    match cut.find(char::is_whitespace) {
+        Some(space) => cut[space..].trim_start(),
+        None => cut,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tail_keeps_whole_words() {
+        assert_eq!(tail("short", 20), "short");
+        assert_eq!(tail("The quick brown fox", 9), "brown fox");
+        assert_eq!(tail("The quick brown fox", 10), "brown fox");
+        assert_eq!(tail("Supercalifragilistic", 5), "istic");
+        assert_eq!(tail("Grüße aus Köln", 8), "aus Köln");
+        assert_eq!(tail("anything", 0), "");
+    }
+
+    #[test]
+    fn test_commit_rolls_per_session() {
+        let contexts = SegmentContexts::default();
+        assert_eq!(contexts.get("a"), None);
+
+        contexts.commit("a", " Meeting with Anna. ", 30);
+        contexts.commit("a", "We agreed on the Q3 roadmap.", 30);
+        contexts.commit("b", "Other session", 30);
+        assert_eq!(contexts.get("a").as_deref(), Some("We agreed on the Q3 roadmap."));
+        assert_eq!(contexts.get("b").as_deref(), Some("Other session"));
+
+        contexts.clear("a");
+        assert_eq!(contexts.get("a"), None);
+    }
+}
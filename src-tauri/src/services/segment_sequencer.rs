@@ -0,0 +1,245 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{watch, OwnedSemaphorePermit, Semaphore};
+use tokio::time::timeout;
+
+/// How long later segments wait for an earlier one that never reached the backend
+const MISSING_SEGMENT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Insertion order of one recording's batch segments, numbered by the VAD session
+#[derive(Debug, Default)]
+struct Order {
+    /// Sequence number allowed to insert next
+    next: u64,
+    /// Segments that reached the backend and are still being processed
+    pending: BTreeSet<u64>,
+    /// Finished segments waiting for an earlier one
+    finished: BTreeSet<u64>,
+    /// The recording has stopped; the session is dropped once nothing is pending
+    closed: bool,
+}
+
+impl Order {
+    fn arrive(&mut self, sequence: u64) {
+        if sequence >= self.next {
+            self.pending.insert(sequence);
+        }
+    }
+
+    /// Mark a segment as inserted (or failed) and let the following ones through
+    fn finish(&mut self, sequence: u64) {
+        self.pending.remove(&sequence);
+        if sequence >= self.next {
+            self.finished.insert(sequence);
+        }
+        while self.finished.remove(&self.next) {
+            self.next += 1;
+        }
+    }
+
+    /// Give up on every segment before `before` that never arrived, so a waiting
+    /// segment is only held back by earlier ones still in progress. Returns the skipped numbers.
+    fn skip_missing(&mut self, before: u64) -> Vec<u64> {
+        let skipped: Vec<u64> = (self.next..before)
+            .filter(|sequence| !self.pending.contains(sequence) && !self.finished.contains(sequence))
+            .collect();
+        for &sequence in &skipped {
+            self.finish(sequence);
+        }
+        skipped
+    }
+
+    fn is_drained(&self) -> bool {
+        self.closed && self.pending.is_empty()
+    }
+}
+
+struct SessionOrder {
+    order: watch::Sender<Order>,
+    /// Limits how many segments of the session are transcribed at once
+    slots: Arc<Semaphore>,
+}
+
+type Sessions = Arc<Mutex<HashMap<String, Arc<SessionOrder>>>>;
+
+/// Per-recording pipeline for batch segments: up to `max_parallel` segments are
+/// transcribed at once, but text is inserted strictly in VAD order.
+/// Keyed by the VAD session id.
+#[derive(Default)]
+pub struct SegmentSequencer {
+    sessions: Sessions,
+}
+
+impl SegmentSequencer {
+    /// Register segment `sequence` of `session_id`. Dropping the ticket marks the
+    /// segment finished, so an error or an empty transcript never blocks the queue.
+    pub fn arrive(&self, session_id: &str, sequence: u64, max_parallel: usize) -> SegmentTicket {
+        let session = self
+            .sessions
+            .lock()
+            .unwrap()
+            .entry(session_id.to_string())
+            .or_insert_with(|| {
+                Arc::new(SessionOrder {
+                    order: watch::Sender::new(Order::default()),
+                    slots: Arc::new(Semaphore::new(max_parallel.max(1))),
+                })
+            })
+            .clone();
+        session.order.send_modify(|order| order.arrive(sequence));
+
+        SegmentTicket {
+            session_id: session_id.to_string(),
+            sequence,
+            session,
+            sessions: self.sessions.clone(),
+        }
+    }
+
+    /// The recording stopped; forget the session once its segments are inserted
+    pub fn close(&self, session_id: &str) {
+        let mut sessions = self.sessions.lock().unwrap();
+        let Some(session) = sessions.get(session_id) else {
+            return;
+        };
+        let mut drained = false;
+        session.order.send_modify(|order| {
+            order.closed = true;
+            drained = order.is_drained();
+        });
+        if drained {
+            sessions.remove(session_id);
+        }
+    }
+}
+
+/// One segment's place in its session's order
+pub struct SegmentTicket {
+    session_id: String,
+    sequence: u64,
+    session: Arc<SessionOrder>,
+    sessions: Sessions,
+}
+
+impl SegmentTicket {
+    /// Wait for a free transcription slot; hold the permit while the provider runs
+    pub async fn transcription_slot(&self) -> Option<OwnedSemaphorePermit> {
+        self.session.slots.clone().acquire_owned().await.ok()
+    }
+
+    /// Wait until every earlier segment has been inserted, failed, or never arrived
+    pub async fn wait_turn(&self) {
+        let mut order = self.session.order.subscribe();
+        loop {
+            match timeout(MISSING_SEGMENT_TIMEOUT, order.wait_for(|order| order.next >= self.sequence)).await {
+                Ok(_) => return,
+                Err(_) => {
+                    let mut skipped = Vec::new();
+                    self.session.order.send_if_modified(|order| {
+                        skipped = order.skip_missing(self.sequence);
+                        !skipped.is_empty()
+                    });
+                    if !skipped.is_empty() {
+                        eprintln!(
+                            "[Sequencer] {}: segments {:?} never arrived, continuing without them",
+                            self.session_id, skipped
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// False once the recording has stopped
+    pub fn is_open(&self) -> bool {
+        !self.session.order.borrow().closed
+    }
+}
+
+impl Drop for SegmentTicket {
+    fn drop(&mut self) {
+        let mut drained = false;
+        self.session.order.send_modify(|order| {
+            order.finish(self.sequence);
+            drained = order.is_drained();
+        });
+        if drained {
+            let mut sessions = self.sessions.lock().unwrap();
+            if sessions
+                .get(&self.session_id)
+                .is_some_and(|session| Arc::ptr_eq(session, &self.session))
+            {
+                sessions.remove(&self.session_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_inserts_in_sequence() {
+        let mut order = Order::default();
+        for sequence in 0..3 {
+            order.arrive(sequence);
+        }
+
+        // Segment 2 finishes first but has to wait for 0 and 1
+        order.finish(2);
+        assert_eq!(order.next, 0);
+        order.finish(0);
+        assert_eq!(order.next, 1);
+        order.finish(1);
+        assert_eq!(order.next, 3);
+        assert!(order.pending.is_empty() && order.finished.is_empty());
+    }
+
+    #[test]
+    fn test_order_skips_missing_segment() {
+        let mut order = Order::default();
+        order.arrive(0);
+        order.arrive(2);
+
+        // Segment 0 is still being transcribed, so nothing is skipped
+        assert_eq!(order.skip_missing(0), Vec::<u64>::new());
+        order.finish(0);
+        assert_eq!(order.next, 1);
+
+        // Segment 1 never arrived
+        assert_eq!(order.skip_missing(2), vec![1]);
+        assert_eq!(order.next, 2);
+
+        // A segment arriving after it was skipped does not rewind the order
+        order.arrive(1);
+        order.finish(1);
+        assert_eq!(order.next, 2);
+    }
+
+    #[test]
+    fn test_order_skips_consecutive_gaps_at_once() {
+        let mut order = Order::default();
+        order.arrive(0);
+        order.arrive(3);
+        order.arrive(5);
+        order.finish(0);
+
+        // 1, 2 and 4 were lost; 3 is still in progress, so 5 waits only for it
+        assert_eq!(order.skip_missing(5), vec![1, 2, 4]);
+        assert_eq!(order.next, 3);
+        order.finish(3);
+        assert_eq!(order.next, 5);
+    }
+
+    #[test]
+    fn test_order_drains_after_close() {
+        let mut order = Order::default();
+        order.arrive(0);
+        order.closed = true;
+        assert!(!order.is_drained());
+        order.finish(0);
+        assert!(order.is_drained());
+    }
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+/// Entries kept in `transcript_history.json`; the oldest are dropped first
+const MAX_ENTRIES: usize = 200;
+
+/// A transcript kept for later reference
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub text: String,
+    /// When the audio was recorded, in milliseconds since the Unix epoch
+    pub recorded_at: i64,
+    /// Transcribed later from the offline queue instead of being inserted
+    #[serde(default)]
+    pub recovered: bool,
+}
+
+fn history_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join("transcript_history.json"))
+}
+
+/// Stored history, oldest first (empty if missing or unreadable)
+pub fn load(app: &AppHandle) -> Vec<HistoryEntry> {
+    history_path(app).map(|path| read_entries(&path)).unwrap_or_default()
+}
+
+/// Append entries and save, keeping the newest `MAX_ENTRIES`
+pub fn append(app: &AppHandle, entries: Vec<HistoryEntry>) -> Result<(), String> {
+    let path = history_path(app)?;
+    let mut history = read_entries(&path);
+    push_capped(&mut history, entries, MAX_ENTRIES);
+    let json = serde_json::to_string_pretty(&history).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| e.to_string())
+}
+
+fn read_entries(path: &Path) -> Vec<HistoryEntry> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn push_capped(history: &mut Vec<HistoryEntry>, entries: Vec<HistoryEntry>, max_entries: usize) {
+    history.extend(entries);
+    history.sort_by_key(|entry| entry.recorded_at);
+    if history.len() > max_entries {
+        history.drain(..history.len() - max_entries);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(text: &str, recorded_at: i64) -> HistoryEntry {
+        HistoryEntry {
+            text: text.to_string(),
+            recorded_at,
+            recovered: true,
+        }
+    }
+
+    #[test]
+    fn test_push_capped_keeps_newest_in_order() {
+        let mut history = vec![entry("a", 1), entry("c", 3)];
+        push_capped(&mut history, vec![entry("d", 4), entry("b", 2)], 3);
+        let texts: Vec<&str> = history.iter().map(|entry| entry.text.as_str()).collect();
+        assert_eq!(texts, vec!["b", "c", "d"]);
+    }
+}
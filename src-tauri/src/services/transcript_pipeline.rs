@@ -0,0 +1,243 @@
+use regex::Regex;
+
+use crate::services::word_correction;
+use crate::voice_commands::{interpret, Item, VoiceCommands};
+
+/// Options for building a transcript pipeline, captured once per session
+pub struct PipelineOptions {
+    /// Keep provider formatting; when false text is lowercased and punctuation stripped
+    pub smart_format: bool,
+    /// Custom words for fuzzy correction (empty disables correction)
+    pub custom_words: Vec<String>,
+    pub word_correction_threshold: f64,
+    /// Voice command set, or None when voice commands are disabled
+    pub voice_commands: Option<VoiceCommands>,
+}
+
+/// Result of running one transcript through the pipeline
+#[derive(Debug, Default)]
+pub struct PipelineOutput {
+    /// Text to insert and voice command actions to execute, in spoken order
+    pub items: Vec<Item>,
+    /// Corrected transcript before voice commands, for status updates
+    pub transcript: String,
+    /// Whether any voice command matched
+    pub had_command: bool,
+}
+
+/// Text processing shared by every streaming provider:
+/// noise cleanup, formatting, word correction, voice commands and text assembly.
+/// Pure, so it can be tested without a WebSocket.
+pub struct TranscriptPipeline {
+    options: PipelineOptions,
+    noise_re: Regex,
+    space_re: Regex,
+}
+
+impl TranscriptPipeline {
+    pub fn new(options: PipelineOptions) -> Self {
+        Self {
+            options,
+            noise_re: Regex::new(r"\[.*?\]|\(.*?\)").unwrap(),
+            space_re: Regex::new(r"\s+").unwrap(),
+        }
+    }
+
+    pub fn voice_commands(&self) -> Option<&VoiceCommands> {
+        self.options.voice_commands.as_ref()
+    }
+
+    /// Remove non-speech tags like "[music]" or "(laughs)" and collapse whitespace
+    pub fn clean_noise(&self, text: &str) -> String {
+        let cleaned = self.noise_re.replace_all(text, " ");
+        self.space_re.replace_all(&cleaned, " ").trim().to_string()
+    }
+
+    /// Noise cleanup, formatting and word correction
+    fn prepare(&self, text: &str) -> String {
+        let cleaned = self.clean_noise(text);
+        let formatted = if self.options.smart_format {
+            cleaned
+        } else {
+            normalize_whisper_transcript(&cleaned)
+        };
+
+        if self.options.custom_words.is_empty() {
+            formatted
+        } else {
+            word_correction::apply_custom_words(
+                &formatted,
+                &self.options.custom_words,
+                self.options.word_correction_threshold,
+            )
+        }
+    }
+
+    /// Process a committed utterance. The inserted text ends with a space
+    /// unless the utterance ends with a key action.
+    pub fn process_utterance(&self, text: &str) -> PipelineOutput {
+        let transcript = self.prepare(text);
+        if transcript.is_empty() {
+            return PipelineOutput::default();
+        }
+
+        let Some(voice_commands) = self.voice_commands() else {
+            // No voice commands - insert directly with space
+            return PipelineOutput {
+                items: vec![Item::Text(format!("{} ", transcript))],
+                transcript,
+                ..Default::default()
+            };
+        };
+
+        let mut interpretation = interpret(&transcript, voice_commands);
+        interpretation.add_trailing_space();
+
+        PipelineOutput {
+            items: interpretation.items,
+            transcript,
+            had_command: interpretation.had_command,
+        }
+    }
+
+    /// Process buffered words from a word-delta provider. The text keeps the
+    /// provider's spacing: a leading space is restored when the first word had one.
+    pub fn process_words(&self, text: &str, has_leading_space: bool) -> PipelineOutput {
+        let transcript = self.prepare(text);
+        if transcript.is_empty() {
+            return PipelineOutput::default();
+        }
+
+        let (mut items, had_command) = match self.voice_commands() {
+            Some(voice_commands) => {
+                let interpretation = interpret(&transcript, voice_commands);
+                (interpretation.items, interpretation.had_command)
+            }
+            None => (vec![Item::Text(transcript.clone())], false),
+        };
+
+        if has_leading_space {
+            if let Some(Item::Text(text)) = items.first_mut() {
+                if !text.starts_with(' ') {
+                    text.insert(0, ' ');
+                }
+            }
+        }
+
+        PipelineOutput {
+            items,
+            transcript,
+            had_command,
+        }
+    }
+}
+
+/// Normalize a transcript when smart formatting is off (matches Electron app behavior):
+/// lowercase and strip punctuation, keeping apostrophes for contractions like "don't" or "dell'auto"
+pub fn normalize_whisper_transcript(text: &str) -> String {
+    // Lowercase the text
+    let lower = text.to_lowercase();
+
+    // Remove all punctuation characters except apostrophes
+    let cleaned: String = lower
+        .chars()
+        .filter(|c| !matches!(c, '.' | ',' | '/' | '#' | '!' | '$' | '%' | '^' | '&' | '*'
+                              | ';' | ':' | '{' | '}' | '=' | '_' | '~' | '('
+                              | ')' | '[' | ']' | '"' | '<' | '>' | '?' | '@' | '+' | '|'
+                              | '\\' | '-'))
+        .collect();
+
+    // Replace multiple spaces with single space and trim
+    cleaned.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voice_commands::{CommandAction, CommandPacks};
+
+    /// All text the output inserts, concatenated
+    fn inserted(output: &PipelineOutput) -> String {
+        output
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Text(text) => Some(text.as_str()),
+                Item::Action(_) => None,
+            })
+            .collect()
+    }
+
+    fn pipeline(smart_format: bool, voice_commands: bool) -> TranscriptPipeline {
+        TranscriptPipeline::new(PipelineOptions {
+            smart_format,
+            custom_words: Vec::new(),
+            word_correction_threshold: 0.18,
+            voice_commands: voice_commands.then(|| VoiceCommands::new_with_language("en", &CommandPacks::builtin(), &Default::default())),
+        })
+    }
+
+    #[test]
+    fn test_plain_utterance_gets_trailing_space() {
+        let output = pipeline(true, false).process_utterance("Hello world.");
+        assert_eq!(output.items, vec![Item::Text("Hello world. ".to_string())]);
+    }
+
+    #[test]
+    fn test_noise_tags_removed() {
+        let output = pipeline(true, false).process_utterance("(laughs) Hello [music] there");
+        assert_eq!(inserted(&output), "Hello there ");
+        assert!(pipeline(true, false).process_utterance("[silence]").items.is_empty());
+    }
+
+    #[test]
+    fn test_normalizes_without_smart_format() {
+        let output = pipeline(false, false).process_utterance("Don't Stop, Now!");
+        assert_eq!(inserted(&output), "don't stop now ");
+    }
+
+    #[test]
+    fn test_punctuation_command_in_utterance() {
+        let output = pipeline(true, true).process_utterance("hello question mark");
+        assert_eq!(inserted(&output), "hello? ");
+        assert!(output.had_command);
+    }
+
+    #[test]
+    fn test_key_command_has_no_trailing_space() {
+        let output = pipeline(true, true).process_utterance("press enter");
+        assert_eq!(output.items, vec![Item::Action(CommandAction::KeyPress("enter".to_string()))]);
+    }
+
+    #[test]
+    fn test_text_and_commands_keep_spoken_order() {
+        let output = pipeline(true, true).process_utterance("Hi Anna, comma. Press enter. See you period");
+        assert_eq!(
+            output.items,
+            vec![
+                Item::Text("Hi Anna,".to_string()),
+                Item::Action(CommandAction::KeyPress("enter".to_string())),
+                Item::Text("See you. ".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_words_keep_leading_space() {
+        let output = pipeline(true, true).process_words("world", true);
+        assert_eq!(inserted(&output), " world");
+        let output = pipeline(true, false).process_words("world", false);
+        assert_eq!(inserted(&output), "world");
+    }
+
+    #[test]
+    fn test_word_correction_applied() {
+        let pipeline = TranscriptPipeline::new(PipelineOptions {
+            smart_format: true,
+            custom_words: vec!["Dictate".to_string()],
+            word_correction_threshold: 0.3,
+            voice_commands: None,
+        });
+        assert_eq!(pipeline.process_utterance("open dictat").transcript, "open Dictate");
+    }
+}
@@ -31,4 +31,4 @@ mod session_manager;
 
 pub use silero::SileroVad;
 pub use smoothed::SmoothedVad;
-pub use session_manager::VadSessionManager;
+pub use session_manager::{FinalSegment, VadSessionManager};
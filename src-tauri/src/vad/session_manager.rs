@@ -1,4 +1,5 @@
 use anyhow::Result;
+use serde::Serialize;
 use serde_json::json;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
@@ -22,6 +23,15 @@ pub struct VadSession {
     // State
     last_speech_time: Instant,
     is_recording: bool,
+    /// Number given to the next emitted segment, so transcripts can be inserted in order
+    next_sequence: u64,
+}
+
+/// Audio left in the buffer when a session stops, numbered after the emitted segments
+#[derive(Serialize)]
+pub struct FinalSegment {
+    pub audio_data: Vec<u8>,
+    pub sequence: u64,
 }
 
 impl VadSession {
@@ -39,8 +49,17 @@ impl VadSession {
             silence_duration_ms,
             last_speech_time: Instant::now(),
             is_recording: false,
+            next_sequence: 0,
         }
     }
+
+    /// Emit the buffered audio as the next numbered segment and clear the buffer
+    fn emit_buffer(&mut self, app_handle: &AppHandle) -> Result<(), anyhow::Error> {
+        VadSessionManager::emit_segment(app_handle, &self.session_id, self.next_sequence, &self.audio_buffer)?;
+        self.next_sequence += 1;
+        self.audio_buffer.clear();
+        Ok(())
+    }
 }
 
 #[derive(Clone)]
@@ -143,13 +162,7 @@ impl VadSessionManager {
                             if silence_duration.as_millis() as u64 > session.silence_duration_ms {
                                 // Emit speech segment
                                 if !session.audio_buffer.is_empty() {
-                                    Self::emit_segment(
-                                        &app_handle,
-                                        &session.session_id,
-                                        &session.audio_buffer,
-                                    )?;
-
-                                    session.audio_buffer.clear();
+                                    session.emit_buffer(&app_handle)?;
                                     session.is_recording = false;
                                 }
                             }
@@ -163,12 +176,7 @@ impl VadSessionManager {
                 if buffer_duration_ms > session.chunk_duration_ms {
                     // Force emit segment
                     if !session.audio_buffer.is_empty() {
-                        Self::emit_segment(
-                            &app_handle,
-                            &session.session_id,
-                            &session.audio_buffer,
-                        )?;
-                        session.audio_buffer.clear();
+                        session.emit_buffer(&app_handle)?;
                     }
                 }
             }
@@ -180,7 +188,7 @@ impl VadSessionManager {
     }
 
     /// Stop session and return final buffered audio
-    pub fn stop_session(&self, session_id: String) -> Result<Vec<u8>, String> {
+    pub fn stop_session(&self, session_id: String) -> Result<FinalSegment, String> {
         let mut sessions = self.sessions.lock().unwrap();
         let session = sessions
             .get_mut(&session_id)
@@ -198,7 +206,10 @@ impl VadSessionManager {
         session.audio_buffer.clear();
         session.is_recording = false;
 
-        Ok(final_audio)
+        Ok(FinalSegment {
+            audio_data: final_audio,
+            sequence: session.next_sequence,
+        })
     }
 
     /// Destroy session and clean up resources
@@ -212,6 +223,7 @@ impl VadSessionManager {
     fn emit_segment(
         app_handle: &AppHandle,
         session_id: &str,
+        sequence: u64,
         audio_buffer: &[f32],
     ) -> Result<(), anyhow::Error> {
         let pcm16_bytes = Self::f32_to_pcm16_bytes(audio_buffer);
@@ -221,6 +233,7 @@ impl VadSessionManager {
             "speech_segment_ready",
             json!({
                 "session_id": session_id,
+                "sequence": sequence,
                 "audio_data": pcm16_bytes,
                 "duration_ms": duration_ms
             }),
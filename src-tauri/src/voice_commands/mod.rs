@@ -1,46 +1,83 @@
 use std::collections::HashMap;
-use regex::Regex;
-
-mod en;
-mod it;
-mod es;
-mod fr;
-mod de;
-mod pt;
-mod ja;
-mod nl;
-mod zh;
-mod ru;
+
+mod packs;
+
+pub use packs::CommandPacks;
+
+/// User voice commands keyed by the language tag of a command pack (see `new_with_language`),
+/// each mapping phrase → action. An empty action removes the pack's phrase.
+pub type CustomCommands = HashMap<String, HashMap<String, String>>;
 
 pub struct VoiceCommands {
     commands: HashMap<String, String>,
+    /// Lowercased phrases with their actions, longest first, for left-to-right matching
+    phrases: Vec<(Vec<char>, String)>,
+    /// Values for `{name}` placeholders in macro text
+    variables: HashMap<String, String>,
 }
 
 impl VoiceCommands {
-    /// Create voice commands for a specific transcription language
-    /// Falls back to English for unsupported languages or "multilingual"
-    pub fn new_with_language(language: &str) -> Self {
-        let commands = match language {
-            "it" => it::commands(),
-            "es" => es::commands(),
-            "fr" => fr::commands(),
-            "de" => de::commands(),
-            "pt" => pt::commands(),
-            "ja" => ja::commands(),
-            "nl" => nl::commands(),
-            "zh" => zh::commands(),
-            "ru" => ru::commands(),
-            _ => en::commands(),  // English default for "en", "multilingual", or unknown
-        };
-        Self { commands }
+    /// Create voice commands for a specific transcription language from the closest
+    /// pack (`pt-BR` → `pt` → `en`, so "multilingual" gets English), merged with the
+    /// user's commands for that pack's tag and every more specific one (`pt`, then
+    /// `pt-BR`), which override or remove its phrases
+    pub fn new_with_language(language: &str, packs: &CommandPacks, custom: &CustomCommands) -> Self {
+        let (tag, pack) = packs.resolve(language);
+        let mut commands = pack.commands.clone();
+        for tag in packs::override_chain(language, tag) {
+            for (_, overrides) in custom.iter().filter(|(key, _)| packs::normalize_tag(key) == tag) {
+                apply_overrides(&mut commands, overrides);
+            }
+        }
+        Self::from_commands(commands)
     }
-    
 
-    
-    pub fn get_commands(&self) -> &HashMap<String, String> {
-        &self.commands
+    fn from_commands(commands: HashMap<String, String>) -> Self {
+        let mut phrases: Vec<(Vec<char>, String)> = commands
+            .iter()
+            .map(|(phrase, action)| (phrase.chars().map(lower_char).collect::<Vec<char>>(), action.clone()))
+            .filter(|(phrase, _)| !phrase.is_empty())
+            .collect();
+        // Longest phrase wins ("question mark" before "mark"); ties are broken alphabetically
+        phrases.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        Self { commands, phrases, variables: HashMap::new() }
+    }
+
+    /// Use `variables` for `{name}` placeholders in macro text (see `expand_template`)
+    pub fn with_variables(mut self, variables: HashMap<String, String>) -> Self {
+        self.variables = variables;
+        self
+    }
+
+    /// Macro steps with the placeholders in their text filled in
+    fn fill_templates(&self, steps: Vec<CommandAction>) -> Vec<CommandAction> {
+        let now = chrono::Local::now();
+        steps
+            .into_iter()
+            .map(|step| match step {
+                CommandAction::InsertText(template) => {
+                    CommandAction::InsertText(expand_template(&template, &self.variables, now))
+                }
+                step => step,
+            })
+            .collect()
     }
 
+    /// Longest phrase matching at `start` on word boundaries, with its end index
+    fn match_at(&self, lower: &[char], start: usize) -> Option<(usize, &str)> {
+        if start > 0 && is_word_char(lower[start - 1]) {
+            return None;
+        }
+        self.phrases.iter().find_map(|(phrase, action)| {
+            let end = start + phrase.len();
+            let matches = lower.get(start..end) == Some(phrase.as_slice())
+                && lower.get(end).is_none_or(|c| !is_word_char(*c));
+            matches.then_some((end, action.as_str()))
+        })
+    }
+    
+
+    
     /// Helper to efficiently strip punctuation for command matching
     fn clean_text_for_matching(text: &str) -> String {
         text.to_lowercase()
@@ -88,7 +125,23 @@ impl VoiceCommands {
     }
 }
 
-#[derive(Debug)]
+/// Add or replace user phrases; an empty action removes the phrase
+fn apply_overrides(commands: &mut HashMap<String, String>, overrides: &HashMap<String, String>) {
+    for (phrase, action) in overrides {
+        let phrase = phrase.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+        if phrase.is_empty() {
+            continue;
+        }
+        let action = action.trim();
+        if action.is_empty() {
+            commands.remove(&phrase);
+        } else {
+            commands.insert(phrase, action.to_string());
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum CommandAction {
     InsertText(String),      // Punctuation or text to insert
     KeyPress(String),        // Single key (enter, backspace, space, tab)
@@ -96,10 +149,47 @@ pub enum CommandAction {
     DeleteLastWord,          // Special: delete last word
     Rewrite,                 // Special: trigger text rewrite
     PauseDictation,          // Special: pause/stop dictation
+    Wait(u64),               // Macro step: pause for milliseconds
+    Macro(Vec<CommandAction>), // Steps run in order ("text:Best regards,|enter|text:{name}")
 }
 
+/// Separates the steps of a macro action
+const MACRO_SEPARATOR: char = '|';
+/// Longest `wait:` step, so a typo cannot stall dictation
+const MAX_MACRO_WAIT_MS: u64 = 10_000;
+
 impl CommandAction {
     pub fn from_action_string(action: &str) -> Self {
+        if action.contains(MACRO_SEPARATOR) {
+            if let Some(steps) = Self::parse_macro(action) {
+                return CommandAction::Macro(steps);
+            }
+        }
+        Self::from_step(action)
+    }
+
+    /// Steps separated by `|`: actions as in `from_action_string`, `wait:<ms>`, or
+    /// `text:<template>` for text that would otherwise read as a key ("text:enter")
+    fn parse_macro(action: &str) -> Option<Vec<CommandAction>> {
+        let steps: Vec<&str> = action.split(MACRO_SEPARATOR).map(str::trim).collect();
+        if steps.len() < 2 || steps.iter().any(|step| step.is_empty()) {
+            return None;
+        }
+        Some(
+            steps
+                .into_iter()
+                .map(|step| match step.strip_prefix("text:") {
+                    Some(text) => CommandAction::InsertText(text.to_string()),
+                    None => Self::from_step(step),
+                })
+                .collect(),
+        )
+    }
+
+    fn from_step(action: &str) -> Self {
+        if let Some(ms) = action.strip_prefix("wait:").and_then(|ms| ms.trim().parse::<u64>().ok()) {
+            return CommandAction::Wait(ms.min(MAX_MACRO_WAIT_MS));
+        }
         match action {
             "enter" => CommandAction::KeyPress("enter".to_string()),
             "backspace" => CommandAction::KeyPress("backspace".to_string()),
@@ -133,104 +223,455 @@ impl CommandAction {
     }
 }
 
-pub struct ProcessedText {
-    pub remaining_text: String,
-    pub processed_text: String,
-    pub actions: Vec<CommandAction>,
-    pub had_key_action: bool,
-    pub had_any_command: bool,
+/// Fill `{name}` placeholders in macro text from `variables`, then the built-in
+/// `{date}` and `{time}`. Unknown placeholders are kept as written.
+pub fn expand_template(
+    template: &str,
+    variables: &HashMap<String, String>,
+    now: chrono::DateTime<chrono::Local>,
+) -> String {
+    let mut expanded = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        expanded.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let name = after
+            .find('}')
+            .map(|close| &after[..close])
+            .filter(|name| !name.is_empty() && name.chars().all(is_word_char));
+        let Some(name) = name else {
+            expanded.push('{');
+            rest = after;
+            continue;
+        };
+        let value = match variables.get(name) {
+            Some(value) => value.clone(),
+            None => match name {
+                "date" => now.format("%Y-%m-%d").to_string(),
+                "time" => now.format("%H:%M").to_string(),
+                _ => format!("{{{}}}", name),
+            },
+        };
+        expanded.push_str(&value);
+        rest = &after[name.len() + 1..];
+    }
+    expanded.push_str(rest);
+    expanded
 }
 
-pub fn process_voice_commands(text: &str, voice_commands: &VoiceCommands) -> ProcessedText {
-    // Keep original casing for text that gets inserted as dictation
-    let mut remaining = text.trim().to_string();
-    // Lowercase copy used only for command detection (models may capitalize first words)
-    let mut remaining_lower = remaining.to_lowercase();
-    let mut processed = String::new();
-    let mut actions = Vec::new();
-    let mut had_key_action = false;
-    let mut had_any_command = false;
-    
-    // Process each voice command
-    for (phrase, action) in voice_commands.get_commands() {
-        // Escape regex special characters
-        let escaped = regex::escape(phrase);
-        // Create word boundary regex (case insensitive)
-        let pattern = format!(r"\b{}\b", escaped);
-        
-        if let Ok(re) = Regex::new(&format!("(?i){}", pattern)) {
-            let cmd_action = CommandAction::from_action_string(action);
-            
-            // Handle special cases that need different processing
-            match &cmd_action {
-                CommandAction::DeleteLastWord => {
-                    // Remove the command phrase and delete last word before it
-                    if re.is_match(&remaining_lower) {
-                        remaining = re.replace_all(&remaining, "").to_string();
-                        remaining_lower = remaining.to_lowercase();
-                        // Remove last word from processed text
-                        let words: Vec<&str> = processed.trim().split_whitespace().collect();
-                        if !words.is_empty() {
-                            processed = words[..words.len() - 1].join(" ");
-                            if !processed.is_empty() {
-                                processed.push(' ');
-                            }
-                        }
-                        actions.push(cmd_action);
-                        had_key_action = true;
-                        had_any_command = true;
-                    }
-                }
-                CommandAction::Rewrite | CommandAction::PauseDictation => {
-                    // Remove the command phrase and add action
-                    if re.is_match(&remaining_lower) {
-                        remaining = re.replace_all(&remaining, "").to_string().trim().to_string();
-                        remaining_lower = remaining.to_lowercase();
-                        processed = processed.trim_end().to_string();
-                        actions.push(cmd_action);
-                        had_key_action = true;
-                        had_any_command = true;
-                    }
-                }
-                CommandAction::KeyPress(_) | CommandAction::KeyCombo(_, _) => {
-                    // Remove the command phrase and add key action
-                    if re.is_match(&remaining_lower) {
-                        remaining = re.replace_all(&remaining, "").to_string();
-                        remaining_lower = remaining.to_lowercase();
-                        actions.push(cmd_action);
-                        had_key_action = true;
-                        had_any_command = true;
-                    }
-                }
-                CommandAction::InsertText(text) => {
-                    // Replace command phrase with punctuation/text
-                    if re.is_match(&remaining_lower) {
-                        remaining = re.replace_all(&remaining, "").to_string();
-                        remaining_lower = remaining.to_lowercase();
-                        processed.push_str(text);
-                        processed.push(' ');
-                        had_any_command = true;
-                    }
-                }
+/// One step of an interpreted transcript, executed in order
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+    /// Text to insert, with spoken punctuation already applied
+    Text(String),
+    /// Key press or special action (never `InsertText`)
+    Action(CommandAction),
+}
+
+/// A transcript split into text and command actions in spoken order
+#[derive(Debug, Default)]
+pub struct Interpretation {
+    pub items: Vec<Item>,
+    /// Whether any voice command matched, including punctuation
+    pub had_command: bool,
+}
+
+impl Interpretation {
+    /// Add the space that separates dictated chunks when the transcript ends in text
+    /// (not after an action, an opening bracket, a joiner or CJK text)
+    pub fn add_trailing_space(&mut self) {
+        if let Some(Item::Text(text)) = self.items.last_mut() {
+            if text.chars().last().is_some_and(|c| !is_cjk(c) && !OPENERS.contains(&c) && !JOINERS.contains(&c)) {
+                text.push(' ');
             }
         }
     }
-    
-    // After a voice command, the model often adds trailing punctuation (. ,)
-    // because the user paused after speaking the command. Strip it.
-    let mut final_remaining = remaining.trim().to_string();
-    if had_any_command {
-        // Remove trailing period/comma that the model added after the command
-        final_remaining = final_remaining.trim_end_matches(|c: char| c == '.' || c == ',').trim().to_string();
-        // Also remove leading period/comma (command was at the end of the chunk)
-        final_remaining = final_remaining.trim_start_matches(|c: char| c == '.' || c == ',').trim().to_string();
+
+    /// The text items joined with spaces, for delivery where actions cannot run
+    pub fn text_only(&self) -> String {
+        self.items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Text(text) => Some(text.trim()),
+                Item::Action(_) => None,
+            })
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
     }
-    
-    ProcessedText {
-        remaining_text: final_remaining,
-        processed_text: processed,
-        actions,
-        had_key_action,
-        had_any_command,
+}
+
+/// Punctuation written with a space before and none after
+const OPENERS: &[char] = &['(', '[', '{', '¿', '¡', '«', '（', '「', '『'];
+/// Punctuation written without spaces on either side
+const JOINERS: &[char] = &['-', '@', '/'];
+/// Punctuation written with spaces on both sides
+const SPACED: &[char] = &['='];
+/// Quotes open or close depending on whether one is already open
+const QUOTES: &[char] = &['"', '\''];
+
+/// Lowercase one char to one char, so lowercased text keeps its char indices
+fn lower_char(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Word characters for phrase boundaries (as regex `\w`)
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Scripts and punctuation written without spaces between words
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3000}'..='\u{30FF}'   // CJK punctuation, Hiragana, Katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{FF00}'..='\u{FFEF}' // Full-width forms
+    )
+}
+
+/// Model punctuation around a spoken command ("Hello, comma, world.") is dropped
+fn is_command_padding(c: char) -> bool {
+    c.is_whitespace() || c == '.' || c == ','
+}
+
+/// Assembles items, merging dictated text and spoken punctuation into text runs
+#[derive(Default)]
+struct ItemBuilder {
+    items: Vec<Item>,
+    text: String,
+    /// The last punctuation binds to the next word (opener or joiner)
+    glue_next: bool,
+    quote_open: bool,
+}
+
+impl ItemBuilder {
+    fn push_text(&mut self, text: &str) {
+        let Some(first) = text.chars().next() else {
+            return;
+        };
+        if let Some(last) = self.text.chars().last() {
+            if !self.glue_next && !last.is_whitespace() && !is_cjk(last) && !is_cjk(first) {
+                self.text.push(' ');
+            }
+        }
+        self.text.push_str(text);
+        self.glue_next = false;
+    }
+
+    fn push_punctuation(&mut self, punctuation: &str) {
+        let Some(first) = punctuation.chars().next() else {
+            return;
+        };
+        let opens = OPENERS.contains(&first) || (QUOTES.contains(&first) && !self.quote_open);
+        if QUOTES.contains(&first) {
+            self.quote_open = !self.quote_open;
+        }
+
+        if opens || SPACED.contains(&first) {
+            // Separate from the previous word like a new word would be
+            let glue = self.glue_next;
+            self.push_text(punctuation);
+            self.glue_next = opens || glue;
+        } else {
+            // Closing punctuation and joiners attach to the previous word
+            let trimmed = self.text.trim_end().len();
+            self.text.truncate(trimmed);
+            self.text.push_str(punctuation);
+            self.glue_next = JOINERS.contains(&first);
+        }
+    }
+
+    fn push_action(&mut self, action: CommandAction) {
+        self.flush_text();
+        self.items.push(Item::Action(action));
+        self.glue_next = false;
+    }
+
+    fn flush_text(&mut self) {
+        if !self.text.is_empty() {
+            self.items.push(Item::Text(std::mem::take(&mut self.text)));
+        }
+    }
+
+    fn finish(mut self) -> Vec<Item> {
+        self.flush_text();
+        self.items
+    }
+}
+
+/// Walk the transcript left to right, matching the longest command phrase at each
+/// word boundary, and return text and actions in the order they were spoken.
+/// Spoken punctuation is merged into the surrounding text.
+pub fn interpret(text: &str, voice_commands: &VoiceCommands) -> Interpretation {
+    let chars: Vec<char> = text.trim().chars().collect();
+    // Lowercase copy used only for command detection (models may capitalize first words).
+    // One char per char keeps indices aligned with `chars`.
+    let lower: Vec<char> = chars.iter().copied().map(lower_char).collect();
+
+    let mut builder = ItemBuilder::default();
+    let mut had_command = false;
+    let mut text_start = 0;
+    let mut index = 0;
+    while index < chars.len() {
+        let Some((end, action)) = voice_commands.match_at(&lower, index) else {
+            index += 1;
+            continue;
+        };
+
+        // Dictated text before the command, without the model's pause punctuation
+        let before: String = chars[text_start..index].iter().collect();
+        builder.push_text(before.trim_end_matches(is_command_padding).trim_start());
+
+        match CommandAction::from_action_string(action) {
+            CommandAction::InsertText(punctuation) => builder.push_punctuation(&punctuation),
+            CommandAction::Macro(steps) => builder.push_action(CommandAction::Macro(voice_commands.fill_templates(steps))),
+            action => builder.push_action(action),
+        }
+        had_command = true;
+
+        // Skip the model's punctuation after the command ("press enter.")
+        index = end;
+        while index < chars.len() && is_command_padding(chars[index]) {
+            index += 1;
+        }
+        text_start = index;
+    }
+    let rest: String = chars[text_start..].iter().collect();
+    builder.push_text(rest.trim());
+
+    Interpretation {
+        items: builder.finish(),
+        had_command,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(language: &str, text: &str) -> Vec<Item> {
+        let mut interpretation = interpret(text, &VoiceCommands::new_with_language(language, &CommandPacks::builtin(), &CustomCommands::new()));
+        interpretation.add_trailing_space();
+        interpretation.items
+    }
+
+    fn text(value: &str) -> Item {
+        Item::Text(value.to_string())
+    }
+
+    fn key(name: &str) -> Item {
+        Item::Action(CommandAction::KeyPress(name.to_string()))
+    }
+
+    fn combo(modifier: &str, name: &str) -> Item {
+        Item::Action(CommandAction::KeyCombo(modifier.to_string(), name.to_string()))
+    }
+
+    #[test]
+    fn test_interpret_en() {
+        assert_eq!(run("en", "hello comma world period"), vec![text("hello, world. ")]);
+        assert_eq!(run("en", "Hello, comma, world. Period."), vec![text("Hello, world. ")]);
+        assert_eq!(run("en", "is it done question mark"), vec![text("is it done? ")]);
+        assert_eq!(
+            run("en", "Dear Anna press enter thanks for the update period"),
+            vec![text("Dear Anna"), key("enter"), text("thanks for the update. ")]
+        );
+        assert_eq!(
+            run("en", "select all press copy"),
+            vec![combo("control", "a"), combo("control", "c")]
+        );
+        assert_eq!(
+            run("en", "call f open parenthesis x close parenthesis"),
+            vec![text("call f (x) ")]
+        );
+        assert_eq!(
+            run("en", "she said open quote hi close quote"),
+            vec![text("she said \"hi\" ")]
+        );
+        assert_eq!(run("en", "mail me at sign example"), vec![text("mail me@example ")]);
+        assert_eq!(
+            run("en", "first draft delete that"),
+            vec![text("first draft"), Item::Action(CommandAction::DeleteLastWord)]
+        );
+        // Phrases only match whole words
+        assert_eq!(run("en", "the periodic table"), vec![text("the periodic table ")]);
+    }
+
+    #[test]
+    fn test_interpret_it() {
+        assert_eq!(run("it", "ciao virgola mondo punto"), vec![text("ciao, mondo. ")]);
+        assert_eq!(run("it", "va bene punto interrogativo"), vec![text("va bene? ")]);
+        assert_eq!(run("it", "fatto premi invio"), vec![text("fatto"), key("enter")]);
+    }
+
+    #[test]
+    fn test_interpret_es() {
+        assert_eq!(run("es", "hola coma mundo punto"), vec![text("hola, mundo. ")]);
+        assert_eq!(run("es", "listo presionar enter"), vec![text("listo"), key("enter")]);
+        assert_eq!(run("es", "seleccionar todo"), vec![combo("control", "a")]);
+    }
+
+    #[test]
+    fn test_interpret_fr() {
+        assert_eq!(run("fr", "bonjour virgule le monde point"), vec![text("bonjour, le monde. ")]);
+        assert_eq!(run("fr", "ça va point d'interrogation"), vec![text("ça va? ")]);
+        assert_eq!(run("fr", "merci appuyer sur entrée"), vec![text("merci"), key("enter")]);
+    }
+
+    #[test]
+    fn test_interpret_de() {
+        assert_eq!(run("de", "Hallo Komma Welt Punkt"), vec![text("Hallo, Welt. ")]);
+        assert_eq!(run("de", "wie geht es Fragezeichen"), vec![text("wie geht es? ")]);
+        assert_eq!(run("de", "fertig drücke Enter"), vec![text("fertig"), key("enter")]);
+    }
+
+    #[test]
+    fn test_interpret_pt() {
+        // "ponto final" is longer than "ponto" and wins
+        assert_eq!(run("pt", "olá vírgula mundo ponto final"), vec![text("olá, mundo. ")]);
+        assert_eq!(run("pt", "tudo bem ponto de interrogação"), vec![text("tudo bem? ")]);
+        assert_eq!(run("pt", "pronto pressionar enter"), vec![text("pronto"), key("enter")]);
+    }
+
+    #[test]
+    fn test_interpret_nl() {
+        assert_eq!(run("nl", "hallo komma wereld punt"), vec![text("hallo, wereld. ")]);
+        assert_eq!(run("nl", "klaar druk op enter"), vec![text("klaar"), key("enter")]);
+    }
+
+    #[test]
+    fn test_interpret_ru() {
+        assert_eq!(run("ru", "привет запятая мир точка"), vec![text("привет, мир. ")]);
+        assert_eq!(run("ru", "Готово нажать ввод"), vec![text("Готово"), key("enter")]);
+    }
+
+    #[test]
+    fn test_interpret_ja() {
+        assert_eq!(run("ja", "こんにちは てん 世界 まる"), vec![text("こんにちは、世界。")]);
+        assert_eq!(run("ja", "完了 改行を押す"), vec![text("完了"), key("enter")]);
+    }
+
+    #[test]
+    fn test_interpret_zh() {
+        assert_eq!(run("zh", "你好 逗号 世界 句号"), vec![text("你好，世界。")]);
+        assert_eq!(run("zh", "完成 按下 回车"), vec![text("完成"), key("enter")]);
+    }
+
+    #[test]
+    fn test_custom_commands_override_built_ins() {
+        let mut english = HashMap::new();
+        english.insert(" Stop  Listening ".to_string(), "".to_string());
+        english.insert("new paragraph".to_string(), "enter".to_string());
+        english.insert("period".to_string(), "!".to_string());
+        english.insert("sign off".to_string(), "Best regards".to_string());
+        let custom: CustomCommands = [("en".to_string(), english)].into_iter().collect();
+
+        let voice_commands = VoiceCommands::new_with_language("multilingual", &CommandPacks::builtin(), &custom);
+        assert!(!voice_commands.is_exact_command("stop listening"));
+        assert_eq!(
+            interpret("we stop listening to noise new paragraph great period", &voice_commands).items,
+            vec![text("we stop listening to noise"), key("enter"), text("great!")]
+        );
+        assert_eq!(interpret("sign off", &voice_commands).items, vec![text("Best regards")]);
+
+        // Other languages keep their built-ins
+        let german = VoiceCommands::new_with_language("de", &CommandPacks::builtin(), &custom);
+        assert_eq!(interpret("ja Punkt", &german).items, vec![text("ja.")]);
+    }
+
+    #[test]
+    fn test_custom_commands_follow_the_language_chain() {
+        let pt: HashMap<String, String> = [
+            ("nova linha".to_string(), "enter".to_string()),
+            ("assinatura".to_string(), "Abraços".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        let pt_br: HashMap<String, String> = [("assinatura".to_string(), "Abraço".to_string())].into_iter().collect();
+        let custom: CustomCommands = [("pt".to_string(), pt), ("pt-BR".to_string(), pt_br)].into_iter().collect();
+
+        // pt-BR dictation resolves to the pt pack but still gets its own entries, which win
+        let brazilian = VoiceCommands::new_with_language("pt-BR", &CommandPacks::builtin(), &custom);
+        assert_eq!(
+            interpret("oi nova linha assinatura", &brazilian).items,
+            vec![text("oi"), key("enter"), text("Abraço")]
+        );
+        let portuguese = VoiceCommands::new_with_language("pt", &CommandPacks::builtin(), &custom);
+        assert_eq!(interpret("assinatura", &portuguese).items, vec![text("Abraços")]);
+    }
+
+    #[test]
+    fn test_text_only_drops_actions() {
+        let voice_commands = VoiceCommands::new_with_language("en", &CommandPacks::builtin(), &CustomCommands::new());
+        let interpretation = interpret("Dear Anna comma press enter thanks period", &voice_commands);
+        assert_eq!(interpretation.text_only(), "Dear Anna, thanks.");
+        assert_eq!(interpret("press enter", &voice_commands).text_only(), "");
+    }
+
+    #[test]
+    fn test_macro_actions() {
+        assert_eq!(
+            CommandAction::from_action_string("text:Best regards,|enter|wait:250|text:{name}"),
+            CommandAction::Macro(vec![
+                CommandAction::InsertText("Best regards,".to_string()),
+                CommandAction::KeyPress("enter".to_string()),
+                CommandAction::Wait(250),
+                CommandAction::InsertText("{name}".to_string()),
+            ])
+        );
+        assert_eq!(
+            CommandAction::from_action_string("tab | tab | ctrl+enter | wait:60000"),
+            CommandAction::Macro(vec![
+                CommandAction::KeyPress("tab".to_string()),
+                CommandAction::KeyPress("tab".to_string()),
+                CommandAction::KeyCombo("control".to_string(), "enter".to_string()),
+                CommandAction::Wait(MAX_MACRO_WAIT_MS),
+            ])
+        );
+        // A lone separator is still text
+        assert_eq!(CommandAction::from_action_string("|"), CommandAction::InsertText("|".to_string()));
+
+        let mut english = HashMap::new();
+        english.insert("submit form".to_string(), "tab|tab|enter".to_string());
+        let custom: CustomCommands = [("en".to_string(), english)].into_iter().collect();
+        let voice_commands = VoiceCommands::new_with_language("en", &CommandPacks::builtin(), &custom);
+        let items = interpret("done submit form", &voice_commands).items;
+        assert_eq!(items.len(), 2);
+        assert!(matches!(&items[1], Item::Action(CommandAction::Macro(steps)) if steps.len() == 3));
+
+        // Placeholders are filled from the variables loaded with the commands
+        let mut english = HashMap::new();
+        english.insert("sign off".to_string(), "text:Best regards,|enter|text:{name}".to_string());
+        let custom: CustomCommands = [("en".to_string(), english)].into_iter().collect();
+        let variables = [("name".to_string(), "Anna Rossi".to_string())].into_iter().collect();
+        let voice_commands =
+            VoiceCommands::new_with_language("en", &CommandPacks::builtin(), &custom).with_variables(variables);
+        assert_eq!(
+            interpret("sign off", &voice_commands).items,
+            vec![Item::Action(CommandAction::Macro(vec![
+                CommandAction::InsertText("Best regards,".to_string()),
+                CommandAction::KeyPress("enter".to_string()),
+                CommandAction::InsertText("Anna Rossi".to_string()),
+            ]))]
+        );
+    }
+
+    #[test]
+    fn test_expand_template() {
+        use chrono::TimeZone;
+        let now = chrono::Local.with_ymd_and_hms(2024, 3, 9, 14, 5, 0).unwrap();
+        let variables: HashMap<String, String> = [("name".to_string(), "Anna Rossi".to_string())].into_iter().collect();
+        assert_eq!(expand_template("{name}, {date} {time}", &variables, now), "Anna Rossi, 2024-03-09 14:05");
+        assert_eq!(expand_template("{unknown} {} {a b} {name", &variables, now), "{unknown} {} {a b} {name");
+        assert_eq!(expand_template("ciao {name}!", &variables, now), "ciao Anna Rossi!");
+    }
+
+    #[test]
+    fn test_interpret_without_commands() {
+        let interpretation = interpret("  just text  ", &VoiceCommands::new_with_language("en", &CommandPacks::builtin(), &CustomCommands::new()));
+        assert_eq!(interpretation.items, vec![text("just text")]);
+        assert!(!interpretation.had_command);
+        assert!(interpret("", &VoiceCommands::new_with_language("en", &CommandPacks::builtin(), &CustomCommands::new())).items.is_empty());
     }
 }
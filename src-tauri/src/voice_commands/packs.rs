@@ -0,0 +1,164 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Packs compiled into the app, by language tag
+const BUILTIN_PACKS: &[(&str, &str)] = &[
+    ("en", include_str!("packs/en.json")),
+    ("it", include_str!("packs/it.json")),
+    ("es", include_str!("packs/es.json")),
+    ("fr", include_str!("packs/fr.json")),
+    ("de", include_str!("packs/de.json")),
+    ("pt", include_str!("packs/pt.json")),
+    ("ja", include_str!("packs/ja.json")),
+    ("nl", include_str!("packs/nl.json")),
+    ("zh", include_str!("packs/zh.json")),
+    ("ru", include_str!("packs/ru.json")),
+];
+
+/// Pack used when nothing in a language's tag chain matches
+const FALLBACK_LANGUAGE: &str = "en";
+
+/// Voice commands of one language (`<tag>.json`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommandPack {
+    /// Display name, e.g. "Portuguese (Brazil)"
+    #[serde(default)]
+    pub name: String,
+    /// Phrase → action (same grammar as `CommandAction::from_action_string`)
+    pub commands: HashMap<String, String>,
+}
+
+/// Voice command packs by lowercase language tag: the built-in ones plus packs loaded
+/// from `<app data>/voice_commands`, which add languages or replace a built-in pack
+pub struct CommandPacks {
+    packs: HashMap<String, CommandPack>,
+}
+
+impl CommandPacks {
+    pub fn builtin() -> Self {
+        let packs = BUILTIN_PACKS
+            .iter()
+            .map(|(tag, json)| {
+                let pack = serde_json::from_str(json)
+                    .unwrap_or_else(|e| panic!("Invalid built-in voice command pack '{}': {}", tag, e));
+                (tag.to_string(), pack)
+            })
+            .collect();
+        Self { packs }
+    }
+
+    /// Built-in packs plus every `<tag>.json` in `dir`. Invalid files are skipped.
+    pub fn load(dir: &Path) -> Self {
+        let mut packs = Self::builtin();
+        let Ok(entries) = fs::read_dir(dir) else {
+            return packs;
+        };
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let Some(tag) = path.file_stem().and_then(|stem| stem.to_str()).map(normalize_tag) else {
+                continue;
+            };
+            let pack = fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|content| serde_json::from_str::<CommandPack>(&content).map_err(|e| e.to_string()));
+            match pack {
+                Ok(pack) => {
+                    println!("[Voice Commands] Loaded '{}' pack ({}) from {}", tag, pack.name, path.display());
+                    packs.packs.insert(tag, pack);
+                }
+                Err(e) => eprintln!("[Voice Commands] Skipping pack {}: {}", path.display(), e),
+            }
+        }
+        packs
+    }
+
+    /// Pack for a transcription language and the tag it was found under, trying the
+    /// tag, then its shorter prefixes, then English (`pt-BR` → `pt` → `en`)
+    pub fn resolve(&self, language: &str) -> (&str, &CommandPack) {
+        language_chain(language)
+            .iter()
+            .find_map(|tag| self.packs.get_key_value(tag))
+            .map(|(tag, pack)| (tag.as_str(), pack))
+            .expect("the English pack is built in")
+    }
+}
+
+/// Tags whose custom commands apply to `language` when its pack resolved to `resolved`,
+/// least specific first (`pt`, then `pt-br`), so more specific entries win
+pub fn override_chain(language: &str, resolved: &str) -> Vec<String> {
+    let mut chain: Vec<String> = language_chain(language)
+        .into_iter()
+        .take_while(|tag| tag != resolved)
+        .collect();
+    chain.push(resolved.to_string());
+    chain.reverse();
+    chain
+}
+
+/// Lowercase, `-`-separated form of a language tag (`pt_BR` → `pt-br`)
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim().replace('_', "-").to_lowercase()
+}
+
+/// Tags to look up for a language, most specific first, ending with English
+fn language_chain(language: &str) -> Vec<String> {
+    let mut tag = normalize_tag(language);
+    let mut chain = Vec::new();
+    while !tag.is_empty() {
+        chain.push(tag.clone());
+        match tag.rfind('-') {
+            Some(index) => tag.truncate(index),
+            None => break,
+        }
+    }
+    chain.push(FALLBACK_LANGUAGE.to_string());
+    chain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_packs_parse() {
+        let packs = CommandPacks::builtin();
+        for (tag, _) in BUILTIN_PACKS {
+            let (resolved, pack) = packs.resolve(tag);
+            assert_eq!(resolved, *tag);
+            assert!(!pack.name.is_empty() && !pack.commands.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_language_chain() {
+        assert_eq!(language_chain("pt_BR"), vec!["pt-br", "pt", "en"]);
+        assert_eq!(language_chain("zh-Hant-TW"), vec!["zh-hant-tw", "zh-hant", "zh", "en"]);
+        assert_eq!(language_chain("multilingual"), vec!["multilingual", "en"]);
+        assert_eq!(language_chain(""), vec!["en"]);
+
+        assert_eq!(override_chain("pt-BR", "pt"), vec!["pt", "pt-br"]);
+        assert_eq!(override_chain("ko-KR", "en"), vec!["en", "ko", "ko-kr"]);
+        assert_eq!(override_chain("it", "it"), vec!["it"]);
+    }
+
+    #[test]
+    fn test_load_packs_from_directory() {
+        let dir = std::env::temp_dir().join(format!("dictate_command_packs_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("pt-BR.json"), r#"{"name": "Portuguese (Brazil)", "commands": {"ponto final": "."}}"#).unwrap();
+        fs::write(dir.join("ko.json"), r#"{"commands": {"마침표": "."}}"#).unwrap();
+        fs::write(dir.join("pl.json"), "not json").unwrap();
+
+        let packs = CommandPacks::load(&dir);
+        assert_eq!(packs.resolve("pt-BR").1.name, "Portuguese (Brazil)");
+        assert_eq!(packs.resolve("pt-PT").0, "pt");
+        assert_eq!(packs.resolve("ko-KR").0, "ko");
+        assert_eq!(packs.resolve("pl").0, "en");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
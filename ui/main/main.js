@@ -390,7 +390,7 @@ micButton.addEventListener('pointerdown', (e) => {
             isRecording = false;
             micButton.classList.remove('recording');
             visualizerContainer?.classList.remove('active');
-            status.textContent = i18n.t('main.micAccessDenied');
+            status.textContent = describeStartError(err);
         });
     } else {
         // Play stop cue
@@ -427,7 +427,7 @@ micButton.addEventListener('click', (e) => {
             isRecording = false;
             micButton.classList.remove('recording');
             visualizerContainer?.classList.remove('active');
-            status.textContent = i18n.t('main.micAccessDenied');
+            status.textContent = describeStartError(err);
         });
     } else {
         // Play stop cue
@@ -461,7 +461,7 @@ listen('start-recording', async () => {
             isRecording = false;
             micButton.classList.remove('recording');
             visualizerContainer?.classList.remove('active');
-            status.textContent = i18n.t('main.micAccessDenied');
+            status.textContent = describeStartError(err);
         }
     }
 });
@@ -500,9 +500,10 @@ async function performRewrite() {
             'inception': INCEPTION_API_KEY
         };
         
-        const rewriteApiKey = rewriteApiKeyMap[REWRITE_PROVIDER];
+        const rewriteApiKey = rewriteApiKeyMap[REWRITE_PROVIDER] ?? '';
         
-        if (!rewriteApiKey) {
+        // Providers outside the map (custom endpoint) keep their key in settings, if any
+        if (REWRITE_PROVIDER in rewriteApiKeyMap && !rewriteApiKey) {
             const tooltipAnchor = document.body.classList.contains('compact-mode') ? micButton : rewriteBtn;
             showTemporaryTooltip(tooltipAnchor, i18n.t('main.apiKeyMissing'));
             console.warn(`API key not set for rewrite provider: ${REWRITE_PROVIDER}`);
@@ -558,6 +559,9 @@ async function performRewrite() {
         });
     } catch (error) {
         console.error('Text rewrite error:', error);
+        if (error?.code) {
+            handleProviderError(error, 'rewrite');
+        }
     } finally {
         rewriteBtn.classList.remove('loading');
         
@@ -612,7 +616,7 @@ async function toggleRecording() {
             micButton.classList.remove('recording');
             micWrapper?.classList.remove('active');
             visualizerContainer?.classList.remove('active');
-            status.textContent = i18n.t('main.micAccessDenied');
+            status.textContent = describeStartError(err);
         }
     } else {
         // Play stop cue (may be blocked if not a user gesture)
@@ -643,8 +647,9 @@ async function startRecording() {
             'voxtral': MISTRAL_API_KEY
         };
         
-        const apiKey = apiKeyMap[API_SERVICE];
-        if (!apiKey) {
+        // Providers without a key here are registry-only; the backend reads their key from settings
+        const apiKey = apiKeyMap[API_SERVICE] ?? '';
+        if (API_SERVICE in apiKeyMap && !apiKey) {
             showTemporaryTooltip(micButton, i18n.t('main.apiKeyMissing'));
             throw new Error(`API key not configured for ${API_SERVICE}`);
         }
@@ -658,7 +663,8 @@ async function startRecording() {
             voiceCommandsEnabled: VOICE_COMMANDS_ENABLED,
             pushToTalkEnabled: PUSH_TO_TALK_ENABLED,
             invoke: invoke,
-            audioHelpers: audioHelpers
+            audioHelpers: audioHelpers,
+            onError: (error) => handleProviderError(error, 'transcription')
         });
         
         // Initialize visualizer if needed
@@ -701,6 +707,29 @@ async function startRecording() {
     }
 }
 
+// Show a localized message for a backend provider error ({ code, message }).
+// A rejected API key also opens settings on the key field.
+function handleProviderError(error, kind) {
+    if (!error?.code) return i18n.t('main.apiError');
+    
+    const message = i18n.t(`errors.${error.code}`);
+    const anchor = kind === 'rewrite' && !document.body.classList.contains('compact-mode') ? rewriteBtn : micButton;
+    showTemporaryTooltip(anchor, message);
+    
+    if (error.code === 'auth') {
+        invoke('open_api_key_settings', { target: kind }).catch(e =>
+            console.error('Failed to open API key settings:', e)
+        );
+    }
+    return message;
+}
+
+// Status text when recording fails to start: provider errors (e.g. streaming
+// connection rejected) are localized, anything else is reported as microphone access
+function describeStartError(err) {
+    return err?.code ? handleProviderError(err, 'transcription') : i18n.t('main.micAccessDenied');
+}
+
 // Show a temporary tooltip notification on an element
 function showTemporaryTooltip(targetElement, message, duration = 3000) {
     if (!targetElement) return;
@@ -752,7 +781,9 @@ function getProviderDisplayName(provider) {
         'deepgram': 'Deepgram',
         'cartesia': 'Cartesia',
         'elevenlabs': 'ElevenLabs',
-        'voxtral': 'Voxtral'
+        'voxtral': 'Voxtral',
+        'custom': 'Custom endpoint',
+        'local': 'Local Whisper'
     };
     return names[provider] || provider;
 }
@@ -909,6 +940,74 @@ listen('streaming-partial-clear', async () => {
     }
 });
 
+// Listen for the end of an utterance (silence after speech); drop any partial left in the overlay
+listen('streaming-utterance-end', async (event) => {
+    if (SHOW_TRANSCRIPT_OVERLAY && currentSession?.provider?.sessionId === event.payload) {
+        invoke('update_transcript_overlay', { text: '' }).catch(() => {});
+        needsOverlayReposition = true;
+    }
+});
+
+listen('streaming-speech-started', async () => {
+    console.log('[Streaming] Speech started');
+});
+
+// Listen for streaming provider errors (failed handshake, server error frames, lost connection)
+listen('streaming-error', async (event) => {
+    console.error('[Streaming] Provider error:', event.payload.error);
+    if (isRecording) {
+        handleProviderError(event.payload.error, 'transcription');
+    }
+});
+
+// Listen for streaming sessions closed by the provider before we stopped them
+listen('streaming-closed', async (event) => {
+    if (isRecording && currentSession?.provider?.sessionId === event.payload) {
+        await stopRecording();
+    }
+});
+
+// Listen for dropped streaming sockets; audio keeps being buffered and is replayed on reconnect
+listen('streaming-reconnecting', async (event) => {
+    const { session_id, attempt } = event.payload;
+    console.warn(`[Streaming] Connection lost, reconnecting (attempt ${attempt})`);
+    if (isRecording && currentSession?.provider?.sessionId === session_id) {
+        status.textContent = i18n.t('main.reconnecting');
+    }
+});
+
+listen('streaming-reconnected', async (event) => {
+    console.log('[Streaming] Reconnected');
+    if (isRecording && currentSession?.provider?.sessionId === event.payload) {
+        status.textContent = i18n.t('main.recording');
+        showTemporaryTooltip(micButton, i18n.t('main.reconnected'));
+    }
+});
+
+// Listen for provider attempts in a fallback chain (transcription and rewrite)
+listen('provider-attempt', async (event) => {
+    const { kind, provider, attempt, total, status, error } = event.payload;
+    if (status === 'failed' || status === 'skipped') {
+        console.warn(`[Fallback] ${kind} ${attempt}/${total} ${provider} ${status}:`, error);
+    } else if (status === 'superseded') {
+        console.log(`[Hedging] ${provider} was cancelled, a hedged request answered first`);
+    } else if (status === 'succeeded' && attempt > 1) {
+        console.log(`[Fallback] ${kind} served by ${provider} (${attempt}/${total})`);
+        const anchor = kind === 'rewrite' && !document.body.classList.contains('compact-mode') ? rewriteBtn : micButton;
+        showTemporaryTooltip(anchor, i18n.t('main.fallbackUsed').replace('{provider}', getProviderDisplayName(provider)));
+    }
+});
+
+// Listen for segments saved while offline; they are transcribed once the connection returns
+listen('segment-queued-offline', async () => {
+    showTemporaryTooltip(micButton, i18n.t('main.segmentQueued'), 5000);
+});
+
+// Listen for queued segments transcribed later; their text is in the clipboard, not typed
+listen('offline-segments-recovered', async (event) => {
+    showTemporaryTooltip(micButton, i18n.t('main.offlineRecovered').replace('{count}', event.payload), 5000);
+});
+
 // Load settings on startup
 loadSettings();
 loadAudioCues();
@@ -12,6 +12,7 @@ export class BaseProvider {
         this.voiceCommandsEnabled = config.voiceCommandsEnabled !== false;
         this.pushToTalkEnabled = config.pushToTalkEnabled || false;
         this.invoke = config.invoke; // Tauri invoke function
+        this.onError = config.onError; // Called with provider errors ({ code, message })
         
         // Provider state
         this.isActive = false;
@@ -1,6 +1,8 @@
 /**
- * Batch Provider Base Class
- * Handles segmentation-based transcription for non-streaming providers
+ * Batch Provider
+ * Handles segmentation-based transcription for non-streaming providers.
+ * Every batch provider is resolved by id in the backend registry, so one
+ * class serves them all.
  */
 
 import { BaseProvider } from './base-provider.js';
@@ -11,10 +13,12 @@ export class BatchProvider extends BaseProvider {
     constructor(config) {
         super(config);
         
+        // Registry id passed to transcribe_audio_segment
+        this.serviceName = config.serviceName;
+        
         // VAD session management
         this.vadSessionId = null;
         this.unlistenSegment = null;
-        this.isProcessing = false;
         
         // Audio processing helpers (passed from main.js)
         this.audioHelpers = config.audioHelpers;
@@ -27,6 +31,10 @@ export class BatchProvider extends BaseProvider {
         return 'batch';
     }
 
+    getName() {
+        return this.serviceName;
+    }
+
     /**
      * Start batch transcription (VAD-based or PTT mode)
      */
@@ -73,8 +81,9 @@ export class BatchProvider extends BaseProvider {
                 const audioData = event.payload.audio_data;
                 const durationMs = event.payload.duration_ms;
                 
-                // Convert PCM16 bytes to WAV and transcribe
-                await this.processVadSegment(audioData);
+                // Convert PCM16 bytes to WAV and transcribe. Segments run in parallel;
+                // the backend inserts them in sequence order.
+                await this.processVadSegment(audioData, event.payload.sequence);
             });
             
             // Start audio capture with VAD callback
@@ -107,15 +116,9 @@ export class BatchProvider extends BaseProvider {
 
     /**
      * Process VAD-detected speech segment
+     * @param {number} sequence - Segment number within the VAD session
      */
-    async processVadSegment(pcm16Bytes) {
-        if (this.isProcessing) {
-            console.warn(`[${this.getName()}] Skipping segment - already processing`);
-            return;
-        }
-        
-        this.isProcessing = true;
-        
+    async processVadSegment(pcm16Bytes, sequence) {
         try {
             // Convert PCM16 bytes to Int16Array
             const int16Array = new Int16Array(
@@ -126,21 +129,34 @@ export class BatchProvider extends BaseProvider {
             const wavBytes = this.audioHelpers.encodeWav16kMono(int16Array);
             
             // Send to transcription API
-            await this.transcribeSegment(wavBytes);
+            await this.transcribeSegment(wavBytes, sequence);
         } catch (error) {
             console.error(`[${this.getName()}] VAD segment processing error:`, error);
-        } finally {
-            this.isProcessing = false;
+            this.onError?.(error);
         }
     }
 
 
     /**
-     * Transcribe audio segment (must be implemented by concrete provider)
+     * Transcribe audio segment with the provider selected in settings
      * @param {Uint8Array} wavBytes
+     * @param {number} [sequence] - Segment number within the VAD session (not used in PTT mode)
      */
-    async transcribeSegment(wavBytes) {
-        throw new Error('transcribeSegment() must be implemented by provider');
+    async transcribeSegment(wavBytes, sequence = null) {
+        await this.invoke('transcribe_audio_segment', {
+            audioData: Array.from(wavBytes),
+            apiKey: this.apiKey,
+            apiService: this.serviceName,
+            insertionMode: this.insertionMode,
+            options: {
+                language: this.language,
+                textFormatted: this.smartFormat,
+                voiceCommandsEnabled: this.voiceCommandsEnabled,
+                // Lets the backend carry the previous segment's text as context
+                sessionId: this.vadSessionId,
+                sequence
+            }
+        });
     }
 
     /**
@@ -171,18 +187,19 @@ export class BatchProvider extends BaseProvider {
                     this.pttAudioBuffer = [];
                 } catch (error) {
                     console.error(`[${this.getName()}] PTT transcription error:`, error);
+                    this.onError?.(error);
                 }
             }
         } else {
             // Standard mode: stop VAD and get final segment if any
             if (this.vadSessionId) {
                 try {
-                    const finalAudio = await this.invoke('vad_stop_session', {
+                    const finalSegment = await this.invoke('vad_stop_session', {
                         sessionId: this.vadSessionId
                     });
                     
-                    if (finalAudio && finalAudio.length > 0) {
-                        await this.processVadSegment(finalAudio);
+                    if (finalSegment?.audio_data?.length > 0) {
+                        await this.processVadSegment(finalSegment.audio_data, finalSegment.sequence);
                     }
                     
                     await this.invoke('vad_destroy_session', {
@@ -204,6 +221,5 @@ export class BatchProvider extends BaseProvider {
         
         // Reset state
         this.isActive = false;
-        this.isProcessing = false;
     }
 }
@@ -29,11 +29,13 @@ export class CartesiaProvider extends BaseProvider {
         this.sessionId = await this.invoke('start_streaming_transcription', {
             provider: 'cartesia',
             apiKey: this.apiKey,
-            language: streamLanguage,
-            smartFormat: this.smartFormat,
             insertionMode: this.insertionMode,
-            encoding: null,
-            voiceCommandsEnabled: this.voiceCommandsEnabled
+            options: {
+                language: streamLanguage,
+                smartFormat: this.smartFormat,
+                encoding: null,
+                voiceCommandsEnabled: this.voiceCommandsEnabled
+            }
         });
         
         // Start audio capture with Cartesia callback
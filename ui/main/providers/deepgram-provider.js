@@ -29,11 +29,13 @@ export class DeepgramProvider extends BaseProvider {
         this.sessionId = await this.invoke('start_streaming_transcription', {
             provider: 'deepgram',
             apiKey: this.apiKey,
-            language: streamLanguage,
-            smartFormat: this.smartFormat,
             insertionMode: this.insertionMode,
-            encoding: 'linear16',  // PCM16 format
-            voiceCommandsEnabled: this.voiceCommandsEnabled
+            options: {
+                language: streamLanguage,
+                smartFormat: this.smartFormat,
+                encoding: 'linear16',  // PCM16 format
+                voiceCommandsEnabled: this.voiceCommandsEnabled
+            }
         });
         
         // Start audio capture with PCM16 callback
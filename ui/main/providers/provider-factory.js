@@ -3,11 +3,7 @@
  * Creates appropriate provider instance based on service name
  */
 
-import { GroqProvider } from './groq-provider.js';
-import { GeminiProvider } from './gemini-provider.js';
-import { MistralProvider } from './mistral-provider.js';
-import { SambaNovaProvider } from './sambanova-provider.js';
-import { FireworksProvider } from './fireworks-provider.js';
+import { BatchProvider } from './batch-provider.js';
 import { DeepgramProvider } from './deepgram-provider.js';
 import { CartesiaProvider } from './cartesia-provider.js';
 import { VoxtralProvider } from './voxtral-provider.js';
@@ -16,6 +12,7 @@ import { ElevenLabsProvider } from './elevenlabs-provider.js';
 /**
  * Create a provider instance
  * @param {string} serviceName - The API service name (e.g., 'groq', 'deepgram')
+ *   Any id that is not a streaming provider is treated as a batch provider.
  * @param {Object} config - Provider configuration
  * @param {string} config.apiKey - API key for the service
  * @param {string} config.language - Language code or 'multilingual'
@@ -28,21 +25,6 @@ import { ElevenLabsProvider } from './elevenlabs-provider.js';
  */
 export function createProvider(serviceName, config) {
     switch (serviceName.toLowerCase()) {
-        case 'groq':
-            return new GroqProvider(config);
-        
-        case 'gemini':
-            return new GeminiProvider(config);
-        
-        case 'mistral':
-            return new MistralProvider(config);
-        
-        case 'sambanova':
-            return new SambaNovaProvider(config);
-        
-        case 'fireworks':
-            return new FireworksProvider(config);
-        
         case 'deepgram':
             return new DeepgramProvider(config);
         
@@ -56,7 +38,8 @@ export function createProvider(serviceName, config) {
             return new ElevenLabsProvider(config);
         
         default:
-            throw new Error(`Unknown provider: ${serviceName}`);
+            // Batch providers are resolved by id in the backend registry
+            return new BatchProvider({ ...config, serviceName: serviceName.toLowerCase() });
     }
 }
 
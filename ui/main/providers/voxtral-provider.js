@@ -29,11 +29,13 @@ export class VoxtralProvider extends BaseProvider {
         this.sessionId = await this.invoke('start_streaming_transcription', {
             provider: 'voxtral',
             apiKey: this.apiKey,
-            language: streamLanguage,
-            smartFormat: this.smartFormat,
             insertionMode: this.insertionMode,
-            encoding: 'linear16',
-            voiceCommandsEnabled: this.voiceCommandsEnabled
+            options: {
+                language: streamLanguage,
+                smartFormat: this.smartFormat,
+                encoding: 'linear16',
+                voiceCommandsEnabled: this.voiceCommandsEnabled
+            }
         });
         
         // Start audio capture with PCM16 callback
@@ -0,0 +1,86 @@
+/**
+ * Reusable text input component (single line or multi-line)
+ */
+export class TextField {
+    constructor(id, label, placeholder, { multiline = false } = {}) {
+        this.id = id;
+        this.label = label;
+        this.placeholder = placeholder;
+        this.multiline = multiline;
+        this.changeCallback = null;
+    }
+
+    onChange(callback) {
+        this.changeCallback = callback;
+    }
+
+    render() {
+        const container = document.createElement('div');
+        container.className = 'form-group';
+        container.id = `${this.id}-group`;
+
+        const labelEl = document.createElement('label');
+        labelEl.htmlFor = this.id;
+        labelEl.textContent = this.label;
+
+        // Gradient border wrapper
+        const wrapper = document.createElement('div');
+        wrapper.className = 'focus-gradient-border';
+
+        const input = document.createElement(this.multiline ? 'textarea' : 'input');
+        if (this.multiline) {
+            input.className = 'prompt-textarea';
+        } else {
+            input.type = 'text';
+        }
+        input.id = this.id;
+        input.placeholder = this.placeholder;
+        input.autocomplete = 'off';
+        input.spellcheck = false;
+
+        // Add change listener
+        input.addEventListener('input', () => {
+            if (this.changeCallback) {
+                this.changeCallback(input.value);
+            }
+        });
+
+        wrapper.appendChild(input);
+        container.appendChild(labelEl);
+        container.appendChild(wrapper);
+
+        return container;
+    }
+
+    getValue() {
+        return document.getElementById(this.id)?.value || '';
+    }
+
+    /**
+     * Offer values as autocomplete suggestions (single-line fields only)
+     * @param {Array<string>} values
+     */
+    setSuggestions(values) {
+        const input = document.getElementById(this.id);
+        if (!input || this.multiline) return;
+
+        const listId = `${this.id}-suggestions`;
+        let datalist = document.getElementById(listId);
+        if (!datalist) {
+            datalist = document.createElement('datalist');
+            datalist.id = listId;
+            input.after(datalist);
+            input.setAttribute('list', listId);
+        }
+        datalist.replaceChildren(...values.map(value => {
+            const option = document.createElement('option');
+            option.value = value;
+            return option;
+        }));
+    }
+
+    setValue(value) {
+        const input = document.getElementById(this.id);
+        if (input) input.value = value;
+    }
+}
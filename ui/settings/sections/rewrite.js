@@ -1,5 +1,6 @@
 import { SelectField } from '../components/select-field.js';
 import { PasswordField } from '../components/password-field.js';
+import { TextField } from '../components/text-field.js';
 import { PRESET_PROMPTS } from '../../shared/prompts.js';
 import { i18n } from '../../shared/i18n.js';
 
@@ -16,7 +17,8 @@ export class RewriteSection {
             { value: 'gemini-flash-lite', label: 'Gemini 3.1 Flash Lite' },
             { value: 'gemini-flash', label: 'Gemini 3 Flash' },
             { value: 'mistral', label: 'Mistral Small' },
-            { value: 'inception', label: 'Mercury 2' }
+            { value: 'inception', label: 'Mercury 2' },
+            { value: 'custom', label: i18n.t('rewrite.custom.option') }
         ]);
 
         const placeholder = i18n.t('rewrite.apiKeys.placeholder');
@@ -30,6 +32,13 @@ export class RewriteSection {
             inception: new PasswordField('rewriteInceptionApiKey', `Inception ${apiKeyLabel}`, placeholder)
         };
 
+        // Custom OpenAI-compatible endpoint fields
+        this.customFields = {
+            baseUrl: new TextField('rewriteCustomBaseUrl', i18n.t('rewrite.custom.baseUrl'), 'http://localhost:11434/v1'),
+            model: new TextField('rewriteCustomModel', i18n.t('rewrite.custom.model'), 'llama3.2'),
+            apiKey: new PasswordField('rewriteCustomApiKey', `${apiKeyLabel} (${i18n.t('rewrite.custom.optional')})`, placeholder)
+        };
+
         // Rewrite mode dropdown
         this.rewriteModeField = new SelectField('rewrite-mode', i18n.t('rewrite.mode'), [
             { value: 'grammar_correction', label: i18n.t('rewrite.modes.grammar_correction') },
@@ -61,6 +70,28 @@ export class RewriteSection {
             section.appendChild(fieldEl);
         });
 
+        // Custom endpoint fields (shown only when the custom provider is selected)
+        const customGroup = document.createElement('div');
+        customGroup.id = 'rewrite-custom-endpoint-group';
+        customGroup.style.display = 'none';
+        customGroup.appendChild(this.customFields.baseUrl.render());
+
+        // Model input with a button that loads the endpoint's model list
+        const modelEl = this.customFields.model.render();
+        const modelRow = document.createElement('div');
+        modelRow.className = 'custom-words-input-row';
+        modelRow.appendChild(modelEl.querySelector('.focus-gradient-border'));
+        this.loadModelsButton = document.createElement('button');
+        this.loadModelsButton.type = 'button';
+        this.loadModelsButton.className = 'custom-words-add-btn';
+        this.loadModelsButton.textContent = i18n.t('rewrite.custom.loadModels');
+        modelRow.appendChild(this.loadModelsButton);
+        modelEl.appendChild(modelRow);
+        customGroup.appendChild(modelEl);
+
+        customGroup.appendChild(this.customFields.apiKey.render());
+        section.appendChild(customGroup);
+
         const modeFieldEl = this.rewriteModeField.render();
         modeFieldEl.style.display = 'flex';
         modeFieldEl.style.flexDirection = 'column';
@@ -89,6 +120,8 @@ export class RewriteSection {
             this.handleModeChange(value);
         });
 
+        this.loadModelsButton?.addEventListener('click', () => this.loadCustomModels());
+
         if (this.promptTextarea) {
             this.promptTextarea.addEventListener('input', () => {
                 const currentMode = this.rewriteModeField.getValue();
@@ -117,10 +150,43 @@ export class RewriteSection {
         }
     }
 
+    /**
+     * Fetch the model list from the custom endpoint and offer it as suggestions
+     */
+    async loadCustomModels() {
+        const invoke = window.__TAURI__?.core?.invoke;
+        if (!invoke) return;
+
+        const button = this.loadModelsButton;
+        button.disabled = true;
+        try {
+            const models = await invoke('list_custom_rewrite_models', {
+                baseUrl: this.customFields.baseUrl.getValue().trim(),
+                apiKey: this.customFields.apiKey.getValue().trim()
+            });
+            this.customFields.model.setSuggestions(models);
+            if (!this.customFields.model.getValue() && models.length > 0) {
+                this.customFields.model.setValue(models[0]);
+                document.getElementById(this.customFields.model.id)?.dispatchEvent(new Event('input', { bubbles: true }));
+            }
+            button.title = '';
+        } catch (error) {
+            console.error('[Settings] Failed to load models:', error);
+            button.title = String(error);
+        } finally {
+            button.disabled = false;
+        }
+    }
+
     updateApiKeyVisibility(provider) {
         document.querySelectorAll('.rewrite-api-key').forEach(el => {
             el.style.display = 'none';
         });
+
+        const customGroup = document.getElementById('rewrite-custom-endpoint-group');
+        if (customGroup) {
+            customGroup.style.display = provider === 'custom' ? 'block' : 'none';
+        }
         
         // Map model variants to their shared API key field
         const keyMap = {
@@ -164,13 +230,23 @@ export class RewriteSection {
                 field.setValue(settings[key]);
             }
         });
+
+        const custom = settings.customRewriteEndpoint || {};
+        this.customFields.baseUrl.setValue(custom.base_url || '');
+        this.customFields.model.setValue(custom.model || '');
+        this.customFields.apiKey.setValue(custom.api_key || '');
     }
 
     getValues() {
         const values = {
             rewriteMode: this.rewriteModeField.getValue(),
             rewriteProvider: this.rewriteProviderField.getValue(),
-            customRewritePrompt: this.promptTextarea.value
+            customRewritePrompt: this.promptTextarea.value,
+            customRewriteEndpoint: {
+                base_url: this.customFields.baseUrl.getValue().trim(),
+                model: this.customFields.model.getValue().trim(),
+                api_key: this.customFields.apiKey.getValue().trim()
+            }
         };
         
         Object.entries(this.apiKeyFields).forEach(([provider, field]) => {
@@ -1,15 +1,44 @@
 import { SelectField } from '../components/select-field.js';
 import { PasswordField } from '../components/password-field.js';
+import { TextField } from '../components/text-field.js';
 import { SliderField } from '../components/slider-field.js';
 import { CustomWordsList } from '../components/custom-words-list.js';
 import { ToggleSwitch } from '../components/toggle-switch.js';
 import { i18n } from '../../shared/i18n.js';
 
+const STREAMING_PROVIDERS = ['deepgram', 'elevenlabs', 'cartesia', 'voxtral'];
+
+const KNOWN_PROVIDERS = [
+    'deepgram', 'elevenlabs', 'cartesia', 'voxtral',
+    'groq', 'sambanova', 'fireworks', 'gemini', 'mistral', 'custom'
+];
+
+/**
+ * Parse "name=value" lines into an object of extra form fields
+ */
+function parseExtraFields(text) {
+    const fields = {};
+    text.split('\n').forEach(line => {
+        const index = line.indexOf('=');
+        if (index <= 0) return;
+        const name = line.slice(0, index).trim();
+        if (name) fields[name] = line.slice(index + 1).trim();
+    });
+    return fields;
+}
+
+function formatExtraFields(fields = {}) {
+    return Object.entries(fields).map(([name, value]) => `${name}=${value}`).join('\n');
+}
+
 /**
  * Transcription settings section
  */
 export class TranscriptionSection {
-    constructor() {
+    /**
+     * @param {Array<{id: string, name: string}>} batchProviders - From list_transcription_providers
+     */
+    constructor(batchProviders = []) {
         this.languageField = new SelectField('language-select', i18n.t('transcription.language'), [
             { value: 'multilingual', label: i18n.t('transcription.languages.multilingual') },
             { value: 'en', label: i18n.t('transcription.languages.en') },
@@ -33,7 +62,12 @@ export class TranscriptionSection {
             { value: 'sambanova', label: 'SambaNova Whisper' },
             { value: 'fireworks', label: 'Fireworks Whisper' },
             { value: 'gemini', label: 'Gemini 3.1 Flash Lite' },
-            { value: 'mistral', label: 'Mistral Voxtral' }
+            { value: 'mistral', label: 'Mistral Voxtral' },
+            { value: 'custom', label: i18n.t('transcription.custom.option') },
+            // Registry providers without a curated entry above
+            ...batchProviders
+                .filter(({ id }) => !KNOWN_PROVIDERS.includes(id))
+                .map(({ id, name }) => ({ value: id, label: name }))
         ]);
 
         // API key fields
@@ -50,6 +84,18 @@ export class TranscriptionSection {
             elevenlabs: new PasswordField('elevenlabsApiKey', `ElevenLabs ${apiKeyLabel}`, placeholder)
         };
 
+        // Custom OpenAI-compatible endpoint fields
+        this.customFields = {
+            baseUrl: new TextField('customBaseUrl', i18n.t('transcription.custom.baseUrl'), 'http://localhost:8000/v1'),
+            model: new TextField('customModel', i18n.t('transcription.custom.model'), 'whisper-1'),
+            apiKey: new PasswordField('customApiKey', `${apiKeyLabel} (${i18n.t('transcription.custom.optional')})`, placeholder),
+            extraFields: new TextField('customExtraFields', i18n.t('transcription.custom.extraFields'), 'temperature=0', { multiline: true })
+        };
+
+        // Keep a streaming connection open between dictations (streaming providers only)
+        this.prewarmToggle = new ToggleSwitch('streaming-prewarm', i18n.t('transcription.prewarm'));
+        this.segmentContextToggle = new ToggleSwitch('segment-context', i18n.t('transcription.segmentContext'));
+
         // Word correction components
         this.wordCorrectionToggle = new ToggleSwitch('word-correction-enabled', '');
         
@@ -64,6 +110,9 @@ export class TranscriptionSection {
         );
 
         this.customWordsList = new CustomWordsList('custom-words', i18n.t('transcription.customWords'));
+
+        // Send custom words to the provider so they are recognized up front
+        this.vocabularyBiasingToggle = new ToggleSwitch('vocabulary-biasing', i18n.t('transcription.vocabularyBiasing'));
     }
 
     render() {
@@ -87,6 +136,28 @@ export class TranscriptionSection {
             section.appendChild(fieldEl);
         });
 
+        // Custom endpoint fields (shown only when the custom provider is selected)
+        const customGroup = document.createElement('div');
+        customGroup.id = 'custom-endpoint-group';
+        customGroup.style.display = 'none';
+        Object.values(this.customFields).forEach(field => {
+            customGroup.appendChild(field.render());
+        });
+        section.appendChild(customGroup);
+
+        const prewarmGroup = document.createElement('div');
+        prewarmGroup.id = 'streaming-prewarm-group';
+        prewarmGroup.style.display = 'none';
+        prewarmGroup.appendChild(this.prewarmToggle.render());
+        section.appendChild(prewarmGroup);
+
+        // Segment context toggle (batch providers only)
+        const segmentContextGroup = document.createElement('div');
+        segmentContextGroup.id = 'segment-context-group';
+        segmentContextGroup.style.display = 'none';
+        segmentContextGroup.appendChild(this.segmentContextToggle.render());
+        section.appendChild(segmentContextGroup);
+
         // Word Correction section
         const wordCorrectionGroup = document.createElement('div');
         wordCorrectionGroup.className = 'settings-group';
@@ -115,9 +186,12 @@ export class TranscriptionSection {
         wordCorrectionBody.style.transition = 'opacity 0.2s ease, pointer-events 0.2s ease';
         
         wordCorrectionBody.appendChild(this.wordCorrectionThreshold.render());
-        wordCorrectionBody.appendChild(this.customWordsList.render());
         wordCorrectionGroup.appendChild(wordCorrectionBody);
 
+        // Custom words also feed vocabulary biasing, so they stay editable with correction off
+        wordCorrectionGroup.appendChild(this.customWordsList.render());
+        wordCorrectionGroup.appendChild(this.vocabularyBiasingToggle.render());
+
         section.appendChild(wordCorrectionGroup);
         
         return section;
@@ -165,6 +239,20 @@ export class TranscriptionSection {
             }
         });
         
+        const customGroup = document.getElementById('custom-endpoint-group');
+        if (customGroup) {
+            customGroup.style.display = provider === 'custom' ? 'block' : 'none';
+        }
+
+        const prewarmGroup = document.getElementById('streaming-prewarm-group');
+        if (prewarmGroup) {
+            prewarmGroup.style.display = STREAMING_PROVIDERS.includes(provider) ? 'block' : 'none';
+        }
+        const segmentContextGroup = document.getElementById('segment-context-group');
+        if (segmentContextGroup) {
+            segmentContextGroup.style.display = STREAMING_PROVIDERS.includes(provider) ? 'none' : 'block';
+        }
+
         const relevantField = this.apiKeyFields[mappedProvider];
         if (relevantField) {
             const fieldEl = document.querySelector(`#${relevantField.id}-group`);
@@ -213,6 +301,16 @@ export class TranscriptionSection {
             }
         });
 
+        const custom = settings.customTranscription || {};
+        this.customFields.baseUrl.setValue(custom.base_url || '');
+        this.customFields.model.setValue(custom.model || '');
+        this.customFields.apiKey.setValue(custom.api_key || '');
+        this.customFields.extraFields.setValue(formatExtraFields(custom.extra_fields));
+
+        this.prewarmToggle.setValue(settings.streamingPrewarm === true);
+        this.segmentContextToggle.setValue(settings.segmentContext !== false);
+        this.vocabularyBiasingToggle.setValue(settings.vocabularyBiasing !== false);
+
         if (settings.wordCorrectionThreshold !== undefined) {
             this.wordCorrectionThreshold.setValue(settings.wordCorrectionThreshold);
         }
@@ -233,7 +331,16 @@ export class TranscriptionSection {
             language: this.languageField.getValue(),
             wordCorrectionThreshold: this.wordCorrectionThreshold.getValue(),
             customWords: this.customWordsList.getValue(),
-            wordCorrectionEnabled: this.wordCorrectionToggle.getValue()
+            wordCorrectionEnabled: this.wordCorrectionToggle.getValue(),
+            streamingPrewarm: this.prewarmToggle.getValue(),
+            segmentContext: this.segmentContextToggle.getValue(),
+            vocabularyBiasing: this.vocabularyBiasingToggle.getValue(),
+            customTranscription: {
+                base_url: this.customFields.baseUrl.getValue().trim(),
+                model: this.customFields.model.getValue().trim(),
+                api_key: this.customFields.apiKey.getValue().trim(),
+                extra_fields: parseExtraFields(this.customFields.extraFields.getValue())
+            }
         };
         
         Object.entries(this.apiKeyFields).forEach(([provider, field]) => {
@@ -15,6 +15,7 @@ let isLoadingSettings = false;
 let isSavingSettings = false;
 let currentSection = 'general';
 let sections = {}; // Will be initialized after i18n
+let sidebar = null;
 
 // Sidebar navigation items with SVG icons
 function getSidebarItems() {
@@ -70,10 +71,10 @@ function getSidebarItems() {
 ];
 }
 
-function createSections() {
+function createSections(batchProviders = []) {
     sections = {
         general: new GeneralSection(),
-        transcription: new TranscriptionSection(),
+        transcription: new TranscriptionSection(batchProviders),
         rewrite: new RewriteSection(),
         shortcuts: new ShortcutsSection(),
         ui: new UISection(),
@@ -84,7 +85,7 @@ function createSections() {
 // Initialize UI
 function initializeUI() {
     // Create and render sidebar
-    const sidebar = new Sidebar(getSidebarItems(), handleSectionChange);
+    sidebar = new Sidebar(getSidebarItems(), handleSectionChange);
     document.getElementById('sidebar-container').appendChild(sidebar.render());
     
     // Render all sections (initially hidden)
@@ -127,6 +128,23 @@ function handleSectionChange(sectionId) {
     updateFooterVisibility();
 }
 
+// Show the section holding the active provider's API key and focus the field
+// (requested by the main window when a provider rejects the key)
+function focusApiKey(target) {
+    const sectionId = target === 'rewrite' ? 'rewrite' : 'transcription';
+    sidebar?.setActive(sectionId);
+    
+    const sectionEl = document.getElementById(`${sectionId}-section`);
+    const input = Array.from(sectionEl?.querySelectorAll('.input-with-eye input') || [])
+        .find(el => el.offsetParent !== null);
+    if (input) {
+        input.scrollIntoView({ block: 'center' });
+        input.focus();
+        input.select();
+    }
+}
+window.focusApiKey = focusApiKey;
+
 function updateFooterVisibility() {
     const footer = document.querySelector('.settings-footer');
     if (!footer) return;
@@ -272,6 +290,11 @@ async function loadSettings(loadedSettings) {
             customWords: settings.custom_words || [],
             wordCorrectionThreshold: settings.word_correction_threshold ?? 0.18,
             wordCorrectionEnabled: settings.word_correction_enabled ?? true,
+            customTranscription: settings.custom_transcription || {},
+            streamingPrewarm: settings.streaming_prewarm ?? false,
+            segmentContext: settings.segment_context_enabled ?? true,
+            vocabularyBiasing: settings.vocabulary_biasing ?? true,
+            customRewriteEndpoint: settings.custom_rewrite_endpoint || {},
             prompts: settings.prompts || {}
         };
         
@@ -348,7 +371,12 @@ async function saveSettings() {
             },
             custom_words: transcriptionValues.customWords || [],
             word_correction_threshold: transcriptionValues.wordCorrectionThreshold ?? 0.18,
-            word_correction_enabled: transcriptionValues.wordCorrectionEnabled ?? true
+            word_correction_enabled: transcriptionValues.wordCorrectionEnabled ?? true,
+            custom_transcription: transcriptionValues.customTranscription,
+            streaming_prewarm: transcriptionValues.streamingPrewarm ?? false,
+            segment_context_enabled: transcriptionValues.segmentContext ?? true,
+            vocabulary_biasing: transcriptionValues.vocabularyBiasing ?? true,
+            custom_rewrite_endpoint: rewriteValues.customRewriteEndpoint
         };
         
         await invoke('save_settings', { settings });
@@ -590,12 +618,25 @@ document.addEventListener('DOMContentLoaded', async () => {
     // Initialize i18n
     await i18n.init(settings.app_language);
     
+    // Batch providers registered in the backend (extends the static provider list)
+    const batchProviders = await invoke('list_transcription_providers').catch((e) => {
+        console.error('Failed to list transcription providers:', e);
+        return [];
+    });
+    
     // Create UI sections (using i18n)
-    createSections();
+    createSections(batchProviders);
     
     initializeUI();
     await loadSettings(settings);
     initializeCustomSelects();
+    
+    // Opened after an authentication error: jump to the rejected key
+    const pendingApiKeyFocus = await invoke('take_pending_api_key_focus').catch(() => null);
+    if (pendingApiKeyFocus) {
+        focusApiKey(pendingApiKeyFocus);
+    }
+    
     await checkForUpdates();
     
     // Listen for toggle-view events (from keyboard shortcut) to sync compact mode toggle